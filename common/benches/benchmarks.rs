@@ -0,0 +1,21 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use common::{get_primes_up_to, get_primes_up_to_parallel, PrimeSieve};
+
+fn prime_sieves(c: &mut Criterion) {
+    let limit = 10_000_000;
+
+    c.bench_function("serial sieve", |b| {
+        b.iter(|| get_primes_up_to(black_box(limit)))
+    });
+
+    c.bench_function("parallel sieve", |b| {
+        b.iter(|| get_primes_up_to_parallel(black_box(limit)))
+    });
+
+    c.bench_function("wheel sieve", |b| {
+        b.iter(|| PrimeSieve::new(black_box(limit as u64)))
+    });
+}
+
+criterion_group!(sieves, prime_sieves);
+criterion_main!(sieves);
@@ -0,0 +1,292 @@
+use crate::arith::divisor_sum::divisor_sums;
+use crate::factor::pollard_rho::factorize;
+use std::collections::HashMap;
+
+/// The sum of the proper divisors s(n) = σ(n) - n, or ``None`` if σ(n) overflows
+///
+/// s(1) = 0, and 0 has no aliquot sum.
+pub fn aliquot_sum(n: u64) -> Option<u64> {
+    if n == 0 {
+        return None;
+    }
+    factorize(n).divisor_sum().map(|sigma| sigma - n)
+}
+
+/// Iterator over the aliquot sequence n, s(n), s(s(n)), ...
+///
+/// Ends with the 1, 0 of a terminating sequence, or early if a term overflows. Cycles and
+/// unbounded growth go on forever, so see ``AliquotTable::trace`` to classify a sequence.
+pub struct AliquotSequence {
+    next: Option<u64>,
+}
+
+impl AliquotSequence {
+    pub fn new(n: u64) -> AliquotSequence {
+        AliquotSequence { next: Some(n) }
+    }
+}
+
+impl Iterator for AliquotSequence {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let term = self.next?;
+        self.next = aliquot_sum(term);
+        Some(term)
+    }
+}
+
+/// A cycle of the aliquot sum: a perfect number, an amicable pair or a longer sociable chain
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Chain {
+    /// The members in sequence order, starting from the smallest
+    members: Vec<u64>,
+}
+
+impl Chain {
+    /// Build the chain from its members in sequence order, starting from any of them
+    fn new(mut members: Vec<u64>) -> Chain {
+        let smallest = (0..members.len()).min_by_key(|&i| members[i]).unwrap();
+        members.rotate_left(smallest);
+        Chain { members }
+    }
+
+    /// The members in sequence order, starting from the smallest
+    pub fn members(&self) -> &[u64] {
+        &self.members
+    }
+
+    /// The number of members: 1 for a perfect number, 2 for an amicable pair
+    pub fn length(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn min_member(&self) -> u64 {
+        self.members[0]
+    }
+}
+
+/// Where an aliquot sequence ends up, counting ``steps`` as applications of s
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AliquotOutcome {
+    /// Reaches 0, by way of a prime and then 1
+    Terminates { steps: usize },
+    /// Enters ``chain`` after ``steps``, where the first member of the chain is reached
+    Cycle { steps: usize, chain: Chain },
+    /// Reaches ``value`` after ``steps``, beyond the limit of the table
+    LeavesBound { steps: usize, value: u64 },
+}
+
+/// Aliquot sums for every ``n`` up to a limit, for following many sequences at once.
+///
+/// The sums come from a σ sieve, so this is far cheaper than factorising each term when exploring
+/// every sequence below a bound, such as when searching for amicable pairs or sociable chains.
+pub struct AliquotTable {
+    sums: Vec<u64>,
+}
+
+impl AliquotTable {
+    /// Build the table of s(n) for all ``n <= limit``
+    pub fn new(limit: usize) -> AliquotTable {
+        let mut sums: Vec<u64> = divisor_sums(limit);
+        for (n, sum) in sums.iter_mut().enumerate().skip(1) {
+            *sum -= n as u64;
+        }
+
+        AliquotTable { sums }
+    }
+
+    /// The largest value covered by the table
+    pub fn limit(&self) -> u64 {
+        (self.sums.len() - 1) as u64
+    }
+
+    /// The aliquot sum s(n), for ``1 <= n <= limit``
+    pub fn aliquot_sum(&self, n: u64) -> u64 {
+        assert!(
+            (1..=self.limit()).contains(&n),
+            "{n} is outside the table range [1, {}]",
+            self.limit()
+        );
+        self.sums[n as usize]
+    }
+
+    /// Follow the aliquot sequence from ``n`` until it terminates, cycles or leaves the table
+    pub fn trace(&self, n: u64) -> AliquotOutcome {
+        // The step at which each term was seen, to find where a cycle starts
+        let mut seen = HashMap::new();
+        let mut term = n;
+
+        for steps in 0.. {
+            if term == 0 {
+                return AliquotOutcome::Terminates { steps };
+            }
+            if term > self.limit() {
+                return AliquotOutcome::LeavesBound { steps, value: term };
+            }
+            if let Some(&start) = seen.get(&term) {
+                let mut members = vec![term];
+                let mut member = self.sums[term as usize];
+                while member != term {
+                    members.push(member);
+                    member = self.sums[member as usize];
+                }
+
+                return AliquotOutcome::Cycle {
+                    steps: start,
+                    chain: Chain::new(members),
+                };
+            }
+
+            seen.insert(term, steps);
+            term = self.sums[term as usize];
+        }
+        unreachable!()
+    }
+
+    /// Every cycle whose members are all within the limit, ordered by smallest member
+    ///
+    /// Each value is only followed until it reaches a value seen from an earlier start, so every
+    /// sequence is walked once in total.
+    pub fn chains(&self) -> Vec<Chain> {
+        let limit = self.limit();
+        // The start from which each value was first reached, 0 for unvisited
+        let mut visited_from = vec![0u64; limit as usize + 1];
+        let mut chains = Vec::new();
+        let mut path = Vec::new();
+
+        for start in 1..=limit {
+            path.clear();
+            let mut term = start;
+            while term != 0 && term <= limit && visited_from[term as usize] == 0 {
+                visited_from[term as usize] = start;
+                path.push(term);
+                term = self.sums[term as usize];
+            }
+
+            // Only a return to this start's own path is a new cycle
+            if term != 0 && term <= limit && visited_from[term as usize] == start {
+                let entry = path.iter().position(|&t| t == term).unwrap();
+                chains.push(Chain::new(path[entry..].to_vec()));
+            }
+        }
+
+        chains.sort_unstable_by_key(|chain| chain.min_member());
+        chains
+    }
+
+    /// The amicable pairs ``(a, b)`` with ``a < b <= limit``, where s(a) = b and s(b) = a
+    pub fn amicable_pairs(&self) -> Vec<(u64, u64)> {
+        self.chains()
+            .into_iter()
+            .filter(|chain| chain.length() == 2)
+            .map(|chain| (chain.members[0], chain.members[1]))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sequence() {
+        let sequence: Vec<_> = AliquotSequence::new(12).collect();
+        assert_eq!(sequence, [12, 16, 15, 9, 4, 3, 1, 0]);
+
+        let cycle: Vec<_> = AliquotSequence::new(562).take(5).collect();
+        assert_eq!(cycle, [562, 284, 220, 284, 220]);
+
+        assert_eq!(aliquot_sum(1), Some(0));
+        assert_eq!(aliquot_sum(28), Some(28));
+        assert_eq!(aliquot_sum(0), None);
+    }
+
+    #[test]
+    fn test_table_matches_factorize() {
+        let table = AliquotTable::new(5_000);
+
+        for n in 1..=5_000 {
+            assert_eq!(Some(table.aliquot_sum(n)), aliquot_sum(n), "{n}");
+        }
+    }
+
+    #[test]
+    fn test_trace() {
+        let table = AliquotTable::new(10_000);
+
+        assert_eq!(table.trace(12), AliquotOutcome::Terminates { steps: 7 });
+        assert_eq!(table.trace(1), AliquotOutcome::Terminates { steps: 1 });
+        assert_eq!(
+            table.trace(6),
+            AliquotOutcome::Cycle {
+                steps: 0,
+                chain: Chain::new(vec![6])
+            }
+        );
+        assert_eq!(
+            table.trace(562),
+            AliquotOutcome::Cycle {
+                steps: 1,
+                chain: Chain::new(vec![284, 220])
+            }
+        );
+
+        // 276 is the smallest start whose fate is unknown, and it soon grows past the table
+        match table.trace(276) {
+            AliquotOutcome::LeavesBound { steps, value } => {
+                assert!(value > 10_000);
+                assert_eq!(AliquotSequence::new(276).nth(steps), Some(value));
+            }
+            outcome => panic!("276 should leave the table, not give {outcome:?}"),
+        }
+    }
+
+    #[test]
+    fn test_chains() {
+        let table = AliquotTable::new(20_000);
+        let chains: Vec<_> = table
+            .chains()
+            .into_iter()
+            .map(|chain| chain.members().to_vec())
+            .collect();
+
+        assert_eq!(
+            chains,
+            [
+                vec![6],
+                vec![28],
+                vec![220, 284],
+                vec![496],
+                vec![1_184, 1_210],
+                vec![2_620, 2_924],
+                vec![5_020, 5_564],
+                vec![6_232, 6_368],
+                vec![8_128],
+                vec![10_744, 10_856],
+                vec![12_285, 14_595],
+                vec![12_496, 14_288, 15_472, 14_536, 14_264],
+                vec![17_296, 18_416],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_amicable_numbers() {
+        // Problem 21: the sum of the amicable numbers under 10000
+        let table = AliquotTable::new(9_999);
+        let total: u64 = table.amicable_pairs().iter().map(|&(a, b)| a + b).sum();
+
+        assert_eq!(total, 31_626);
+    }
+
+    #[test]
+    fn test_longest_chain() {
+        // Problem 95: the longest chain with no member over a million
+        let table = AliquotTable::new(1_000_000);
+        let longest = table.chains().into_iter().max_by_key(|chain| chain.length()).unwrap();
+
+        assert_eq!(longest.length(), 28);
+        assert_eq!(longest.min_member(), 14_316);
+    }
+}
@@ -0,0 +1,86 @@
+use num::traits::PrimInt;
+use rayon::prelude::*;
+
+/// Numbers per chunk of the table, small enough for the chunk to stay in cache while it is sieved
+const CHUNK_SIZE: usize = 1 << 16;
+
+/// The sum of the divisors σ(n) for every ``n <= limit``, with σ(0) left as zero
+///
+/// Each divisor is added to all of its multiples, taking O(limit log limit) time in total. This is
+/// done a chunk of the table at a time: within a chunk each divisor ``d <= sqrt(n)`` is added
+/// along with its partner ``n / d``, so a chunk only loops over the divisors up to the square root
+/// of its end, and the writes stay in cache rather than striding across the whole table.
+///
+/// The largest σ(n) is around ``5 * limit`` for limits up to 10^9, so ``u32`` entries are enough
+/// up to 10^8 and halve the memory compared to ``u64``.
+pub fn divisor_sums<T: PrimInt>(limit: usize) -> Vec<T> {
+    let mut sums = vec![T::zero(); limit + 1];
+
+    for (chunk, sums) in sums.chunks_mut(CHUNK_SIZE).enumerate() {
+        sieve_chunk(chunk * CHUNK_SIZE, sums);
+    }
+
+    sums
+}
+
+/// Parallel version of ``divisor_sums``, with the chunks of the table filled concurrently
+pub fn divisor_sums_parallel<T: PrimInt + Send>(limit: usize) -> Vec<T> {
+    let mut sums = vec![T::zero(); limit + 1];
+
+    sums.par_chunks_mut(CHUNK_SIZE)
+        .enumerate()
+        .for_each(|(chunk, sums)| sieve_chunk(chunk * CHUNK_SIZE, sums));
+
+    sums
+}
+
+/// Fill ``sums`` with σ(n) for ``lo <= n < lo + sums.len()``, leaving σ(0) as zero
+fn sieve_chunk<T: PrimInt>(lo: usize, sums: &mut [T]) {
+    let hi = lo + sums.len();
+
+    for d in 1..=(hi - 1).isqrt() {
+        let divisor = T::from(d).unwrap();
+
+        // Pairs d * q with q >= d, so each pair of divisors is only counted once
+        for q in d.max(lo.div_ceil(d))..=(hi - 1) / d {
+            let entry = &mut sums[d * q - lo];
+            *entry = *entry + divisor;
+            if q != d {
+                *entry = *entry + T::from(q).unwrap();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::arith::linear_sieve::ArithmeticTables;
+
+    #[test]
+    fn test_matches_linear_sieve() {
+        let tables = ArithmeticTables::new(20_000, 1);
+        let sums: Vec<u64> = divisor_sums(20_000);
+
+        for (n, &sum) in sums.iter().enumerate() {
+            assert_eq!(sum, tables.divisor_sigma(n), "{n}");
+        }
+    }
+
+    #[test]
+    fn test_parallel_matches_serial() {
+        for limit in [0, 1, 2, 15, CHUNK_SIZE - 1, CHUNK_SIZE, 3 * CHUNK_SIZE + 7] {
+            let serial: Vec<u32> = divisor_sums(limit);
+            let parallel: Vec<u32> = divisor_sums_parallel(limit);
+
+            assert_eq!(serial, parallel, "{limit}");
+        }
+    }
+
+    #[test]
+    fn test_small_values() {
+        let sums: Vec<u16> = divisor_sums(12);
+
+        assert_eq!(sums, [0, 1, 3, 4, 7, 6, 12, 8, 15, 13, 18, 12, 28]);
+    }
+}
@@ -0,0 +1,244 @@
+use crate::arith::multiplicative::MultiplicativeFunction;
+use num::traits::{One, Zero};
+
+/// Euler's linear sieve, visiting every ``2 <= n <= limit`` exactly once.
+///
+/// Each ``n`` is passed to ``visit`` along with its smallest prime ``p``, the exponent ``e`` of
+/// ``p`` in ``n`` and ``p^e``. Both ``n / p^e`` and ``p^(e - 1)`` are smaller than ``n`` and are
+/// always visited before it, so a table of a multiplicative function can be filled in as the sieve
+/// goes. Every composite is crossed off once, by its smallest prime, giving O(limit) time overall.
+fn linear_sieve<V>(limit: usize, mut visit: V)
+where
+    V: FnMut(usize, usize, u32, usize),
+{
+    assert!(
+        limit <= u32::MAX as usize,
+        "a limit of {limit} is too large for the linear sieve"
+    );
+
+    let mut primes: Vec<usize> = Vec::new();
+    // The smallest prime factor, its exponent and power, with 0 marking the primes not yet reached
+    let mut smallest_factor = vec![0u32; limit + 1];
+    let mut exponent = vec![0u8; limit + 1];
+    let mut smallest_power = vec![0u32; limit + 1];
+
+    for i in 2..=limit {
+        if smallest_factor[i] == 0 {
+            smallest_factor[i] = i as u32;
+            exponent[i] = 1;
+            smallest_power[i] = i as u32;
+            primes.push(i);
+        }
+
+        let (p_i, e_i, pe_i) = (
+            smallest_factor[i] as usize,
+            exponent[i],
+            smallest_power[i] as usize,
+        );
+        visit(i, p_i, e_i as u32, pe_i);
+
+        for &p in &primes {
+            if p > p_i || i * p > limit {
+                break;
+            }
+
+            let multiple = i * p;
+            smallest_factor[multiple] = p as u32;
+            if p == p_i {
+                exponent[multiple] = e_i + 1;
+                smallest_power[multiple] = (pe_i * p) as u32;
+            } else {
+                exponent[multiple] = 1;
+                smallest_power[multiple] = p as u32;
+            }
+        }
+    }
+}
+
+/// The values ``f(n)`` for every ``n <= limit``, with ``f(0)`` left as zero
+pub fn multiplicative_table<F: MultiplicativeFunction>(limit: usize, f: &F) -> Vec<F::Output> {
+    let mut values = vec![F::Output::zero(); limit + 1];
+    if limit >= 1 {
+        values[1] = F::Output::one();
+    }
+
+    linear_sieve(limit, |n, p, e, pe| {
+        values[n] = values[n / pe] * f.at_prime_power(p as u64, e);
+    });
+
+    values
+}
+
+/// Tables of the common arithmetic functions for every ``n`` up to a limit.
+///
+/// All six are filled in together by a single pass of a linear sieve, so this is cheaper than
+/// building each with ``multiplicative_table`` when several are needed. Every function is zero at
+/// ``n = 0``, where it isn't defined.
+pub struct ArithmeticTables {
+    k: u32,
+    totient: Vec<u64>,
+    mobius: Vec<i8>,
+    num_distinct: Vec<u8>,
+    num_total: Vec<u8>,
+    num_divisors: Vec<u32>,
+    divisor_sigma: Vec<u64>,
+}
+
+impl ArithmeticTables {
+    /// Build the tables for all ``n <= limit``, with σ_k summing the ``k``th powers of divisors
+    ///
+    /// σ_k(n) is held as a ``u64``, so large ``k`` will overflow for all but small limits.
+    pub fn new(limit: usize, k: u32) -> ArithmeticTables {
+        let mut tables = ArithmeticTables {
+            k,
+            totient: vec![0; limit + 1],
+            mobius: vec![0; limit + 1],
+            num_distinct: vec![0; limit + 1],
+            num_total: vec![0; limit + 1],
+            num_divisors: vec![0; limit + 1],
+            divisor_sigma: vec![0; limit + 1],
+        };
+        if limit >= 1 {
+            tables.totient[1] = 1;
+            tables.mobius[1] = 1;
+            tables.num_divisors[1] = 1;
+            tables.divisor_sigma[1] = 1;
+        }
+
+        linear_sieve(limit, |n, p, e, pe| {
+            // n = rest * p^e with rest co-prime to p, and both factors already in the tables
+            let rest = n / pe;
+            let pe_k = (pe as u64).pow(k);
+
+            tables.totient[n] = tables.totient[rest] * (pe - pe / p) as u64;
+            tables.mobius[n] = if e == 1 { -tables.mobius[rest] } else { 0 };
+            tables.num_distinct[n] = tables.num_distinct[rest] + 1;
+            tables.num_total[n] = tables.num_total[rest] + e as u8;
+            tables.num_divisors[n] = tables.num_divisors[rest] * (e + 1);
+            tables.divisor_sigma[n] =
+                tables.divisor_sigma[rest] * (tables.divisor_sigma[pe / p] + pe_k);
+        });
+
+        tables
+    }
+
+    /// The largest value covered by the tables
+    pub fn limit(&self) -> usize {
+        self.totient.len() - 1
+    }
+
+    /// Euler's totient φ(n)
+    pub fn totient(&self, n: usize) -> u64 {
+        self.totient[n]
+    }
+
+    /// The Möbius function μ(n)
+    pub fn mobius(&self, n: usize) -> i8 {
+        self.mobius[n]
+    }
+
+    /// The number of distinct prime factors ω(n)
+    pub fn num_distinct_prime_factors(&self, n: usize) -> u32 {
+        self.num_distinct[n] as u32
+    }
+
+    /// The number of prime factors counted with multiplicity Ω(n)
+    pub fn num_prime_factors(&self, n: usize) -> u32 {
+        self.num_total[n] as u32
+    }
+
+    /// The number of divisors τ(n)
+    pub fn num_divisors(&self, n: usize) -> u32 {
+        self.num_divisors[n]
+    }
+
+    /// The sum of the ``k``th powers of the divisors σ_k(n), for the ``k`` given to ``new``
+    pub fn divisor_sigma(&self, n: usize) -> u64 {
+        self.divisor_sigma[n]
+    }
+
+    /// The power ``k`` used by ``divisor_sigma``
+    pub fn sigma_power(&self) -> u32 {
+        self.k
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::arith::multiplicative::{DivisorSigma, Mobius, NumDivisors, Totient};
+    use crate::factorize;
+
+    #[test]
+    fn test_tables_match_factorize() {
+        for k in [0, 1, 2] {
+            let tables = ArithmeticTables::new(5_000, k);
+            assert_eq!(tables.limit(), 5_000);
+
+            for n in 1..=5_000 {
+                let factors = factorize(n as u64);
+
+                assert_eq!(tables.totient(n), Totient.evaluate(&factors), "φ({n})");
+                assert_eq!(tables.mobius(n), Mobius.evaluate(&factors), "μ({n})");
+                assert_eq!(
+                    tables.num_distinct_prime_factors(n),
+                    factors.num_distinct() as u32,
+                    "ω({n})"
+                );
+                assert_eq!(tables.num_prime_factors(n), factors.num_total(), "Ω({n})");
+                assert_eq!(tables.num_divisors(n), NumDivisors.evaluate(&factors), "τ({n})");
+                assert_eq!(
+                    tables.divisor_sigma(n),
+                    DivisorSigma(k).evaluate(&factors),
+                    "σ_{k}({n})"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_sigma_matches_divisors() {
+        let tables = ArithmeticTables::new(1_000, 1);
+
+        for n in 1..=1_000 {
+            let sum: u64 = (1..=n as u64).filter(|d| (n as u64).is_multiple_of(*d)).sum();
+            assert_eq!(tables.divisor_sigma(n), sum, "{n}");
+        }
+    }
+
+    #[test]
+    fn test_multiplicative_table() {
+        let totients = multiplicative_table(10_000, &Totient);
+        let tables = ArithmeticTables::new(10_000, 1);
+
+        assert_eq!(totients[0], 0);
+        for (n, &phi) in totients.iter().enumerate() {
+            assert_eq!(phi, tables.totient(n), "{n}");
+        }
+
+        // A new function only needs its value at prime powers: 2^ω(n) counts unitary divisors
+        struct UnitaryDivisors;
+        impl MultiplicativeFunction for UnitaryDivisors {
+            type Output = u32;
+            fn at_prime_power(&self, _p: u64, _e: u32) -> u32 {
+                2
+            }
+        }
+
+        let unitary = multiplicative_table(10_000, &UnitaryDivisors);
+        for (n, &count) in unitary.iter().enumerate().skip(1) {
+            assert_eq!(count, 1 << tables.num_distinct_prime_factors(n), "{n}");
+        }
+    }
+
+    #[test]
+    fn test_small_limits() {
+        assert_eq!(multiplicative_table(0, &Totient), [0]);
+        assert_eq!(multiplicative_table(1, &Mobius), [0, 1]);
+        assert_eq!(multiplicative_table(6, &Mobius), [0, 1, -1, -1, 0, -1, 1]);
+
+        let tables = ArithmeticTables::new(1, 1);
+        assert_eq!(tables.totient(1), 1);
+        assert_eq!(tables.divisor_sigma(0), 0);
+    }
+}
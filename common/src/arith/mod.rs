@@ -0,0 +1,4 @@
+pub mod divisor_sum;
+pub mod linear_sieve;
+pub mod multiplicative;
+pub mod summatory;
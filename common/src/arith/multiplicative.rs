@@ -0,0 +1,99 @@
+use crate::factor::Factorization;
+use num::traits::{One, Zero};
+
+/// An arithmetic function with ``f(mn) = f(m) f(n)`` whenever ``m`` and ``n`` are co-prime.
+///
+/// Such a function is fixed by its value at each prime power, so that is all an implementation
+/// needs to give. ``multiplicative_table`` then fills in every value up to a limit with a linear
+/// sieve.
+pub trait MultiplicativeFunction {
+    /// ``Zero`` is only used as a placeholder at ``n = 0``, where the function isn't defined
+    type Output: Copy + Zero + One;
+
+    /// The value at ``p^e``, for a prime ``p`` and ``e >= 1``
+    fn at_prime_power(&self, p: u64, e: u32) -> Self::Output;
+
+    /// The value at the integer with the given factorisation
+    fn evaluate(&self, factorization: &Factorization<u64>) -> Self::Output {
+        factorization
+            .factors()
+            .iter()
+            .fold(Self::Output::one(), |value, &(p, e)| {
+                value * self.at_prime_power(p, e)
+            })
+    }
+}
+
+/// Euler's totient φ(n), the number of ``1 <= k <= n`` co-prime to ``n``
+pub struct Totient;
+
+impl MultiplicativeFunction for Totient {
+    type Output = u64;
+
+    fn at_prime_power(&self, p: u64, e: u32) -> u64 {
+        p.pow(e - 1) * (p - 1)
+    }
+}
+
+/// The Möbius function μ(n): 0 if ``n`` has a square factor, otherwise ``(-1)^ω(n)``
+pub struct Mobius;
+
+impl MultiplicativeFunction for Mobius {
+    type Output = i8;
+
+    fn at_prime_power(&self, _p: u64, e: u32) -> i8 {
+        if e == 1 {
+            -1
+        } else {
+            0
+        }
+    }
+}
+
+/// The number of divisors τ(n)
+pub struct NumDivisors;
+
+impl MultiplicativeFunction for NumDivisors {
+    type Output = u32;
+
+    fn at_prime_power(&self, _p: u64, e: u32) -> u32 {
+        e + 1
+    }
+}
+
+/// The sum of the ``k``th powers of the divisors σ_k(n)
+///
+/// ``DivisorSigma(0)`` counts the divisors and ``DivisorSigma(1)`` is the usual divisor sum.
+pub struct DivisorSigma(pub u32);
+
+impl MultiplicativeFunction for DivisorSigma {
+    type Output = u64;
+
+    fn at_prime_power(&self, p: u64, e: u32) -> u64 {
+        let p_k = p.pow(self.0);
+        (0..e).fold(1, |sum, _| sum * p_k + 1)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::factorize;
+
+    #[test]
+    fn test_evaluate() {
+        let n = factorize(720u64); // 2^4 * 3^2 * 5
+
+        assert_eq!(Totient.evaluate(&n), 192);
+        assert_eq!(Mobius.evaluate(&n), 0);
+        assert_eq!(NumDivisors.evaluate(&n), 30);
+        assert_eq!(DivisorSigma(0).evaluate(&n), 30);
+        assert_eq!(DivisorSigma(1).evaluate(&n), 2418);
+        assert_eq!(DivisorSigma(2).evaluate(&n), 341 * 91 * 26);
+
+        let one = factorize(1u64);
+        assert_eq!(Totient.evaluate(&one), 1);
+        assert_eq!(Mobius.evaluate(&one), 1);
+        assert_eq!(Mobius.evaluate(&factorize(30u64)), -1);
+    }
+}
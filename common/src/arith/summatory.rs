@@ -0,0 +1,254 @@
+use crate::arith::linear_sieve::multiplicative_table;
+use crate::arith::multiplicative::Mobius;
+use crate::dirichlet::FloorBlocks;
+use num::rational::Ratio;
+use num::traits::PrimInt;
+
+/// Largest table sieved directly by ``totient_sum`` and ``mertens``, bounding their memory use
+const MAX_SIEVE: u64 = 1 << 25;
+
+/// The totient summatory function Φ(n), the sum of φ(k) for ``1 <= k <= n``
+///
+/// Every ``1 <= a <= b <= n`` has ``gcd(a, b) = d`` for exactly one ``d``, and the pairs with a
+/// given ``d`` are counted by Φ(n / d), so ``n(n + 1) / 2`` is the sum of Φ(n / d) over all
+/// ``d``. Solved with ``summatory_recurrence``: under a second for 10^10, and around ten seconds
+/// for 10^12.
+pub fn totient_sum(n: u64) -> u128 {
+    let threshold = sieve_threshold(n);
+
+    // small[v] = Φ(v) for v <= threshold
+    let mut small: Vec<u64> = (0..=threshold).collect();
+    for p in 2..=threshold as usize {
+        if small[p] == p as u64 {
+            for multiple in (p..=threshold as usize).step_by(p) {
+                small[multiple] -= small[multiple] / p as u64;
+            }
+        }
+    }
+    for v in 1..=threshold as usize {
+        small[v] += small[v - 1];
+    }
+
+    summatory_recurrence(
+        n,
+        threshold,
+        |v| small[v as usize] as u128,
+        |v| v as u128 * (v as u128 + 1) / 2,
+    )
+}
+
+/// The Mertens function M(x), the sum of μ(k) for ``1 <= k <= x``
+///
+/// The sum of μ(d) over the divisors of any ``n > 1`` is zero, so summing over ``n <= x`` leaves
+/// the sum of M(x / d) over all ``d`` equal to 1. Solved with ``summatory_recurrence`` in roughly
+/// O(x^(2/3)) time.
+pub fn mertens(x: u64) -> i64 {
+    let threshold = sieve_threshold(x);
+
+    // small[v] = M(v) for v <= threshold
+    let mobius = multiplicative_table(threshold as usize, &Mobius);
+    let small: Vec<i32> = mobius
+        .iter()
+        .scan(0, |sum, &mu| {
+            *sum += mu as i32;
+            Some(*sum)
+        })
+        .collect();
+
+    summatory_recurrence(x, threshold, |v| small[v as usize] as i64, |_| 1)
+}
+
+/// The number of square-free integers ``1 <= k <= x``
+///
+/// Each ``k`` is counted once for every ``d`` with ``d^2 | k``, and by Möbius inversion the
+/// square-free integers are the sum of ``μ(d) floor(x / d^2)`` over ``d <= sqrt(x)``. Takes
+/// O(sqrt(x)) time and memory.
+pub fn count_squarefree(x: u64) -> u64 {
+    let root = x.isqrt();
+    let mobius = multiplicative_table(root as usize, &Mobius);
+
+    let count: i64 = (1..=root)
+        .map(|d| mobius[d as usize] as i64 * (x / (d * d)) as i64)
+        .sum();
+    count as u64
+}
+
+/// Size of the sieved table for the summatory functions, around ``n^(2/3)`` but never less than
+/// ``sqrt(n)``
+fn sieve_threshold(n: u64) -> u64 {
+    let cbrt = (n as f64).cbrt() as u64;
+    (cbrt * cbrt).min(MAX_SIEVE).max(n.isqrt())
+}
+
+/// Solve ``F(n) = total(n) - sum(F(n / d) for 2 <= d <= n)`` for a summatory function ``F``
+///
+/// This is the form taken by any ``F`` whose Dirichlet convolution with 1 has a known sum
+/// ``total``. Grouping the ``d`` with the same ``v / d`` leaves O(sqrt(v)) work for each value
+/// ``v = n / i``, and only the O(sqrt(n)) distinct ones are ever needed. ``small`` gives ``F(v)``
+/// for ``v <= threshold``, and the rest are memoized by ``i``, for roughly O(n^(2/3)) time in
+/// total when the threshold is around ``n^(2/3)``.
+fn summatory_recurrence<T, S, B>(n: u64, threshold: u64, small: S, total: B) -> T
+where
+    T: PrimInt,
+    S: Fn(u64) -> T,
+    B: Fn(u64) -> T,
+{
+    if n <= threshold {
+        return small(n);
+    }
+
+    // large[i] = F(n / i) for n / i > threshold, filled from the smallest value up
+    let n_large = (n / (threshold + 1)) as usize;
+    let mut large = vec![T::zero(); n_large + 1];
+
+    for i in (1..=n_large).rev() {
+        let v = n / i as u64;
+        let mut value = total(v);
+
+        for block in FloorBlocks::starting_at(v, 2) {
+            let below = if block.quotient <= threshold {
+                small(block.quotient)
+            } else {
+                // n / (i * d) = quotient for every d in the block
+                large[i * block.start as usize]
+            };
+            value = value - T::from(block.end - block.start + 1).unwrap() * below;
+        }
+
+        large[i] = value;
+    }
+
+    large[1]
+}
+
+/// The number of terms in the Farey sequence of order ``n``, 0/1 and 1/1 included
+pub fn farey_length(n: u64) -> u128 {
+    1 + totient_sum(n)
+}
+
+/// The number of reduced fractions ``a / b`` with ``b <= n`` strictly between ``lo`` and ``hi``
+///
+/// Counting every fraction in the interval, reduced or not, is easy for each denominator, and
+/// gives prefix counts ``F(m)`` over the denominators up to ``m``. Each fraction reduces to
+/// exactly one reduced fraction, so ``F(n)`` is the sum of the reduced counts for ``n / d``, and
+/// Möbius inversion recovers the reduced count as the sum of ``μ(d) F(n / d)``. Takes O(n) time
+/// and memory.
+pub fn count_reduced_fractions(n: u64, lo: Ratio<u64>, hi: Ratio<u64>) -> u64 {
+    if n == 0 || lo >= hi {
+        return 0;
+    }
+
+    let (p, q) = (*lo.numer() as u128, *lo.denom() as u128);
+    let (r, s) = (*hi.numer() as u128, *hi.denom() as u128);
+
+    // all_fractions[m] = F(m), counting the a with p / q < a / b < r / s for each b <= m
+    let mut all_fractions = vec![0i64; n as usize + 1];
+    for b in 1..=n as usize {
+        let smallest = p * b as u128 / q + 1;
+        let largest = (r * b as u128).div_ceil(s) - 1;
+        let count = (largest + 1).saturating_sub(smallest);
+
+        all_fractions[b] = all_fractions[b - 1] + count as i64;
+    }
+
+    let mobius = multiplicative_table(n as usize, &Mobius);
+    let reduced: i64 = (1..=n as usize)
+        .map(|d| mobius[d] as i64 * all_fractions[n as usize / d])
+        .sum();
+
+    reduced as u64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::arith::multiplicative::Totient;
+    use crate::factor::pollard_rho::gcd;
+
+    #[test]
+    fn test_matches_sieve() {
+        let phi = multiplicative_table(100_000, &Totient);
+        let mut sum = 0u128;
+
+        for n in 0..=100_000u64 {
+            sum += phi[n as usize] as u128;
+            if n < 3_000 || n.is_multiple_of(997) {
+                assert_eq!(totient_sum(n), sum, "{n}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_known_values() {
+        // Problem 72: the reduced proper fractions with denominators up to a million
+        assert_eq!(totient_sum(1_000_000) - 1, 303_963_552_391);
+        assert_eq!(totient_sum(1_000_000_000), 303_963_551_173_008_414);
+    }
+
+    #[test]
+    fn test_mertens_matches_sieve() {
+        let mobius = multiplicative_table(100_000, &Mobius);
+        let mut sum = 0i64;
+        let mut squarefree = 0u64;
+
+        for x in 0..=100_000u64 {
+            sum += mobius[x as usize] as i64;
+            squarefree += (mobius[x as usize] != 0) as u64;
+
+            if x < 3_000 || x.is_multiple_of(997) {
+                assert_eq!(mertens(x), sum, "{x}");
+                assert_eq!(count_squarefree(x), squarefree, "{x}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_mertens_known_values() {
+        assert_eq!(mertens(1_000), 2);
+        assert_eq!(mertens(1_000_000), 212);
+        assert_eq!(mertens(1_000_000_000), -222);
+    }
+
+    #[test]
+    fn test_count_squarefree_known_values() {
+        assert_eq!(count_squarefree(1_000_000), 607_926);
+        assert_eq!(count_squarefree(1_000_000_000), 607_927_124);
+    }
+
+    #[test]
+    fn test_farey_length() {
+        // F_8 holds the 21 reduced proper fractions with denominators up to 8, plus 0/1 and 1/1
+        assert_eq!(farey_length(8), 23);
+        assert_eq!(farey_length(1), 2);
+        assert_eq!(farey_length(0), 1);
+    }
+
+    #[test]
+    fn test_count_reduced_fractions() {
+        let brute_force = |n: u64, lo: Ratio<u64>, hi: Ratio<u64>| {
+            (1..=n)
+                .flat_map(|b| (0..=b).map(move |a| (a, b)))
+                .filter(|&(a, b)| gcd(a, b) == 1)
+                .filter(|&(a, b)| lo < Ratio::new(a, b) && Ratio::new(a, b) < hi)
+                .count() as u64
+        };
+
+        let intervals = [((1, 3), (1, 2)), ((0, 1), (1, 1)), ((2, 7), (5, 8)), ((1, 2), (1, 3))];
+        for n in [0, 1, 8, 50, 200] {
+            for &((p, q), (r, s)) in &intervals {
+                let (lo, hi) = (Ratio::new(p, q), Ratio::new(r, s));
+                assert_eq!(count_reduced_fractions(n, lo, hi), brute_force(n, lo, hi), "{n}");
+            }
+        }
+
+        // Problem 73
+        let third = Ratio::new(1, 3);
+        let half = Ratio::new(1, 2);
+        assert_eq!(count_reduced_fractions(8, third, half), 3);
+        assert_eq!(count_reduced_fractions(12_000, third, half), 7_295_372);
+
+        // Every reduced proper fraction is strictly between 0 and 1
+        let proper = count_reduced_fractions(1_000, Ratio::from_integer(0), Ratio::from_integer(1));
+        assert_eq!(proper as u128, totient_sum(1_000) - 1);
+    }
+}
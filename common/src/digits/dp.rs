@@ -0,0 +1,372 @@
+use crate::digits::digits_be;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A state machine that reads the digits of a number, most significant first, and decides whether
+/// to accept it.
+///
+/// The fewer distinct states it can reach, the more ``DigitDp`` can share between numbers, so the
+/// state should only keep what later digits need, such as a digit sum modulo some ``k``.
+pub trait DigitAutomaton {
+    type State: Copy + Eq + Hash;
+
+    /// The state before any digits are read
+    fn start(&self) -> Self::State;
+
+    /// The state after reading ``digit``, or ``None`` to reject every number with this prefix
+    fn step(&self, state: Self::State, digit: u32) -> Option<Self::State>;
+
+    fn accept(&self, state: Self::State) -> bool;
+
+    /// Whether leading zeros are left out, so that each number is read as it's written
+    ///
+    /// If not, every number is read with the same number of digits as the bound, padded with
+    /// leading zeros. Either way, 0 itself is read as a single 0 digit at the least.
+    fn skip_leading_zeros(&self) -> bool {
+        true
+    }
+}
+
+/// Counts, sums or lists the numbers ``0 <= n <= bound`` accepted by a ``DigitAutomaton``.
+///
+/// The numbers are walked as a tree of digit prefixes. Once a prefix drops below the bound's own
+/// prefix every completion of it is allowed, so the totals below a node only depend on its
+/// position, its state and whether it's still tight against the bound. These are memoized, which
+/// takes O(digits × states × radix) steps rather than one for every number.
+pub struct DigitDp<A> {
+    automaton: A,
+    radix: u32,
+}
+
+impl<A: DigitAutomaton> DigitDp<A> {
+    /// Run ``automaton`` over the digits of numbers in ``radix``
+    ///
+    /// Panics if the radix is outside 2..=36.
+    pub fn new(automaton: A, radix: u32) -> DigitDp<A> {
+        assert!((2..=36).contains(&radix), "radix must be in 2..=36, not {radix}");
+        DigitDp { automaton, radix }
+    }
+
+    /// The number of accepted ``n <= bound``
+    pub fn count(&self, bound: u64) -> u128 {
+        self.search(bound).root_totals().count
+    }
+
+    /// The sum of the accepted ``n <= bound``
+    pub fn sum(&self, bound: u64) -> u128 {
+        self.search(bound).root_totals().sum
+    }
+
+    /// The number of accepted ``n`` with ``lo <= n <= hi``
+    pub fn count_in_range(&self, lo: u64, hi: u64) -> u128 {
+        if lo > hi {
+            return 0;
+        }
+
+        match lo.checked_sub(1) {
+            Some(below) => self.count(hi) - self.count(below),
+            None => self.count(hi),
+        }
+    }
+
+    /// The accepted ``n <= bound``, in ascending order
+    ///
+    /// Branches with nothing accepted below them are cut off using the memoized counts, so the
+    /// time taken is in proportion to the size of the output.
+    pub fn enumerate(&self, bound: u64) -> Vec<u64> {
+        let mut search = self.search(bound);
+        let mut accepted = Vec::new();
+        let (state, started) = search.root();
+        search.collect(0, state, true, started, 0, &mut accepted);
+        accepted
+    }
+
+    fn search(&self, bound: u64) -> Search<'_, A> {
+        Search {
+            automaton: &self.automaton,
+            radix: self.radix,
+            bound: digits_be(bound, self.radix).collect(),
+            memo: HashMap::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Totals {
+    count: u128,
+    /// The sum of the values of the remaining digits, over every accepted completion
+    sum: u128,
+}
+
+/// The memo for a single bound
+struct Search<'a, A: DigitAutomaton> {
+    automaton: &'a A,
+    radix: u32,
+    /// The digits of the bound, most significant first
+    bound: Vec<u32>,
+    /// Keyed by ``(position, state, tight, started)``, where ``started`` is false while only
+    /// skipped leading zeros have been read
+    memo: HashMap<(usize, A::State, bool, bool), Totals>,
+}
+
+impl<A: DigitAutomaton> Search<'_, A> {
+    fn root(&self) -> (A::State, bool) {
+        (self.automaton.start(), !self.automaton.skip_leading_zeros())
+    }
+
+    fn root_totals(&mut self) -> Totals {
+        let (state, started) = self.root();
+        self.totals(0, state, true, started)
+    }
+
+    /// Where ``digit`` at ``position`` leads, or ``None`` if the automaton rejects it
+    fn transition(
+        &self,
+        position: usize,
+        state: A::State,
+        started: bool,
+        digit: u32,
+    ) -> Option<(A::State, bool)> {
+        let is_last = position + 1 == self.bound.len();
+        if !started && digit == 0 && !is_last {
+            // Still a leading zero, which the automaton never sees
+            Some((state, false))
+        } else {
+            self.automaton.step(state, digit).map(|state| (state, true))
+        }
+    }
+
+    /// The largest digit allowed at ``position``
+    fn max_digit(&self, position: usize, tight: bool) -> u32 {
+        if tight {
+            self.bound[position]
+        } else {
+            self.radix - 1
+        }
+    }
+
+    fn totals(&mut self, position: usize, state: A::State, tight: bool, started: bool) -> Totals {
+        if position == self.bound.len() {
+            let accepted = self.automaton.accept(state) as u128;
+            return Totals {
+                count: accepted,
+                sum: 0,
+            };
+        }
+
+        let key = (position, state, tight, started);
+        if let Some(&totals) = self.memo.get(&key) {
+            return totals;
+        }
+
+        let max_digit = self.max_digit(position, tight);
+        let place = (self.radix as u128).pow((self.bound.len() - position - 1) as u32);
+        let mut totals = Totals::default();
+
+        for digit in 0..=max_digit {
+            if let Some((next, started)) = self.transition(position, state, started, digit) {
+                let below = self.totals(position + 1, next, tight && digit == max_digit, started);
+                totals.count += below.count;
+                totals.sum += digit as u128 * place * below.count + below.sum;
+            }
+        }
+
+        self.memo.insert(key, totals);
+        totals
+    }
+
+    fn collect(
+        &mut self,
+        position: usize,
+        state: A::State,
+        tight: bool,
+        started: bool,
+        prefix: u64,
+        accepted: &mut Vec<u64>,
+    ) {
+        if position == self.bound.len() {
+            if self.automaton.accept(state) {
+                accepted.push(prefix);
+            }
+            return;
+        }
+        if self.totals(position, state, tight, started).count == 0 {
+            return;
+        }
+
+        let max_digit = self.max_digit(position, tight);
+        for digit in 0..=max_digit {
+            if let Some((next, started)) = self.transition(position, state, started, digit) {
+                let prefix = prefix * self.radix as u64 + digit as u64;
+                let tight = tight && digit == max_digit;
+                self.collect(position + 1, next, tight, started, prefix, accepted);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::digits::{digit_sum, digits_le, num_digits};
+    use crate::is_prime;
+
+    /// Accepts the numbers whose digit sum is a multiple of ``modulus``
+    struct DigitSumMultiple {
+        modulus: u32,
+    }
+
+    impl DigitAutomaton for DigitSumMultiple {
+        type State = u32;
+
+        fn start(&self) -> u32 {
+            0
+        }
+
+        fn step(&self, sum: u32, digit: u32) -> Option<u32> {
+            Some((sum + digit) % self.modulus)
+        }
+
+        fn accept(&self, sum: u32) -> bool {
+            sum == 0
+        }
+    }
+
+    /// Accepts the numbers without the digit ``banned``
+    struct Without {
+        banned: u32,
+    }
+
+    impl DigitAutomaton for Without {
+        type State = ();
+
+        fn start(&self) {}
+
+        fn step(&self, _: (), digit: u32) -> Option<()> {
+            (digit != self.banned).then_some(())
+        }
+
+        fn accept(&self, _: ()) -> bool {
+            true
+        }
+    }
+
+    /// Accepts the numbers with exactly one zero digit, optionally counting leading zeros
+    struct OneZero {
+        padded: bool,
+    }
+
+    impl DigitAutomaton for OneZero {
+        type State = u8;
+
+        fn start(&self) -> u8 {
+            0
+        }
+
+        fn step(&self, zeros: u8, digit: u32) -> Option<u8> {
+            let zeros = zeros + (digit == 0) as u8;
+            (zeros <= 1).then_some(zeros)
+        }
+
+        fn accept(&self, zeros: u8) -> bool {
+            zeros == 1
+        }
+
+        fn skip_leading_zeros(&self) -> bool {
+            !self.padded
+        }
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        for radix in [2, 3, 10] {
+            for modulus in [1, 3, 7] {
+                let dp = DigitDp::new(DigitSumMultiple { modulus }, radix);
+                let mut expected = Vec::new();
+
+                for bound in 0..2_000u64 {
+                    if digit_sum(bound, radix).is_multiple_of(modulus) {
+                        expected.push(bound);
+                    }
+
+                    if bound < 300 || bound.is_multiple_of(97) {
+                        let sum: u64 = expected.iter().sum();
+                        assert_eq!(dp.count(bound), expected.len() as u128, "{bound}");
+                        assert_eq!(dp.sum(bound), sum as u128, "{bound}");
+                        assert_eq!(dp.enumerate(bound), expected, "{bound}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_in_range() {
+        let dp = DigitDp::new(Without { banned: 4 }, 10);
+        let brute_force = |lo: u64, hi: u64| {
+            (lo..=hi).filter(|&n| digits_le(n, 10).all(|d| d != 4)).count() as u128
+        };
+
+        for (lo, hi) in [(0, 0), (0, 100), (40, 49), (35, 1_234), (500, 400)] {
+            assert_eq!(dp.count_in_range(lo, hi), brute_force(lo, hi), "[{lo}, {hi}]");
+        }
+
+        // Leading zeros are skipped, but would never be a 4 anyway, so the numbers below 10^18
+        // match up with the 18 digit strings over the other nine digits
+        assert_eq!(dp.count(10u64.pow(18) - 1), 9u128.pow(18));
+    }
+
+    #[test]
+    fn test_leading_zeros() {
+        let brute_force = |width: u32| {
+            (0..1_000u64)
+                .filter(|&n| {
+                    let zeros = digits_le(n, 10).filter(|&d| d == 0).count() as u32;
+                    let padding = width.saturating_sub(num_digits(n, 10));
+                    zeros + padding == 1
+                })
+                .count() as u128
+        };
+
+        // 0, the 9 of the form x0 and the 162 of the form xy0 or x0y
+        let written = DigitDp::new(OneZero { padded: false }, 10);
+        assert_eq!(written.count(999), 172);
+        assert_eq!(written.count(999), brute_force(0));
+
+        // Three digit strings with a single zero in any of the three places
+        let padded = DigitDp::new(OneZero { padded: true }, 10);
+        assert_eq!(padded.count(999), 243);
+        assert_eq!(padded.count(999), brute_force(3));
+        assert_eq!(padded.enumerate(20), [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 20]);
+    }
+
+    /// Reads digits into their value, only allowing prefixes that are prime
+    struct RightTruncatablePrime;
+
+    impl DigitAutomaton for RightTruncatablePrime {
+        type State = u64;
+
+        fn start(&self) -> u64 {
+            0
+        }
+
+        fn step(&self, prefix: u64, digit: u32) -> Option<u64> {
+            let prefix = prefix * 10 + digit as u64;
+            is_prime(prefix).then_some(prefix)
+        }
+
+        fn accept(&self, n: u64) -> bool {
+            // Only need to check the left truncations
+            n > 10 && (1..num_digits(n, 10)).all(|k| is_prime(n % 10u64.pow(k)))
+        }
+    }
+
+    #[test]
+    fn test_truncatable_primes() {
+        // Problem 37: a state per prefix is no use to the memo, but the pruning keeps this cheap
+        let truncatable = DigitDp::new(RightTruncatablePrime, 10).enumerate(1_000_000);
+
+        assert_eq!(truncatable.len(), 11);
+        assert_eq!(truncatable.iter().sum::<u64>(), 748_317);
+    }
+}
@@ -0,0 +1,256 @@
+pub mod dp;
+pub mod palindrome;
+pub mod pandigital;
+pub mod signature;
+
+use num::traits::PrimInt;
+
+/// Iterator over the digits of a non-negative integer in any radix from 2 to 36.
+///
+/// Iterates from the least significant digit, and ``rev`` gives the most significant first. Both
+/// ends are found by division with the matching power of the radix, so neither direction needs
+/// the digits to be collected, and 0 has the single digit 0.
+#[derive(Debug, Clone)]
+pub struct Digits<T> {
+    n: T,
+    radix: T,
+    /// The power of the radix for the lowest digit not yet taken
+    low: T,
+    /// The power of the radix for the highest digit not yet taken
+    high: T,
+    remaining: usize,
+}
+
+impl<T: PrimInt> Digits<T> {
+    /// The digits of ``n`` in ``radix``
+    ///
+    /// Panics if ``n`` is negative or the radix is outside 2..=36.
+    pub fn new(n: T, radix: u32) -> Digits<T> {
+        let radix = to_radix(radix);
+        assert!(n >= T::zero(), "negative numbers have no digits");
+
+        // Stop at the highest power no larger than n, which can't overflow
+        let mut high = T::one();
+        let mut remaining = 1;
+        while n / high >= radix {
+            high = high * radix;
+            remaining += 1;
+        }
+
+        Digits {
+            n,
+            radix,
+            low: T::one(),
+            high,
+            remaining,
+        }
+    }
+
+    fn digit_at(&self, power: T) -> u32 {
+        ((self.n / power) % self.radix).to_u32().unwrap()
+    }
+}
+
+impl<T: PrimInt> Iterator for Digits<T> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let digit = self.digit_at(self.low);
+        self.remaining -= 1;
+        // The power past the highest digit may not fit in T
+        if self.remaining > 0 {
+            self.low = self.low * self.radix;
+        }
+        Some(digit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T: PrimInt> DoubleEndedIterator for Digits<T> {
+    fn next_back(&mut self) -> Option<u32> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let digit = self.digit_at(self.high);
+        self.remaining -= 1;
+        if self.remaining > 0 {
+            self.high = self.high / self.radix;
+        }
+        Some(digit)
+    }
+}
+
+impl<T: PrimInt> ExactSizeIterator for Digits<T> {}
+
+/// The digits of ``n``, least significant first
+pub fn digits_le<T: PrimInt>(n: T, radix: u32) -> Digits<T> {
+    Digits::new(n, radix)
+}
+
+/// The digits of ``n``, most significant first
+pub fn digits_be<T: PrimInt>(n: T, radix: u32) -> std::iter::Rev<Digits<T>> {
+    Digits::new(n, radix).rev()
+}
+
+/// The integer with the given digits, most significant first, or ``None`` if it overflows ``T``
+///
+/// Leading zeros are allowed, and no digits at all gives 0. Panics if a digit isn't below the
+/// radix.
+pub fn from_digits<T, I>(digits: I, radix: u32) -> Option<T>
+where
+    T: PrimInt,
+    I: IntoIterator<Item = u32>,
+{
+    let radix_value: T = to_radix(radix);
+
+    digits.into_iter().try_fold(T::zero(), |n, digit| {
+        assert!(digit < radix, "{digit} is not a digit in radix {radix}");
+        n.checked_mul(&radix_value)?.checked_add(&T::from(digit)?)
+    })
+}
+
+/// The number of digits in ``n``, which is 1 for 0
+pub fn num_digits<T: PrimInt>(n: T, radix: u32) -> u32 {
+    Digits::new(n, radix).len() as u32
+}
+
+/// The sum of the digits of ``n``
+pub fn digit_sum<T: PrimInt>(n: T, radix: u32) -> u32 {
+    Digits::new(n, radix).sum()
+}
+
+/// The product of the digits of ``n``, which never exceeds ``n`` itself
+pub fn digit_product<T: PrimInt>(n: T, radix: u32) -> T {
+    Digits::new(n, radix).fold(T::one(), |product, digit| product * T::from(digit).unwrap())
+}
+
+/// ``n`` with its digits in the opposite order, or ``None`` if that overflows ``T``
+///
+/// Trailing zeros become leading zeros, and are lost: 1200 reverses to 21.
+pub fn reverse_digits<T: PrimInt>(n: T, radix: u32) -> Option<T> {
+    from_digits(digits_le(n, radix), radix)
+}
+
+fn to_radix<T: PrimInt>(radix: u32) -> T {
+    assert!((2..=36).contains(&radix), "radix must be in 2..=36, not {radix}");
+    T::from(radix).expect("the radix doesn't fit in the integer type")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// The digits of ``n`` formatted by the standard library, most significant first
+    fn std_digits(n: u128, radix: u32) -> Vec<u32> {
+        let text = match radix {
+            2 => format!("{n:b}"),
+            8 => format!("{n:o}"),
+            10 => format!("{n}"),
+            16 => format!("{n:x}"),
+            _ => unreachable!(),
+        };
+        text.chars().map(|c| c.to_digit(radix).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_matches_formatting() {
+        let values = (0..2_000u128).chain([u64::MAX as u128, 1 << 100, u128::MAX - 1, u128::MAX]);
+
+        for n in values {
+            for radix in [2, 8, 10, 16] {
+                let expected = std_digits(n, radix);
+
+                assert_eq!(digits_be(n, radix).collect::<Vec<_>>(), expected, "{n} {radix}");
+                let mut little_endian: Vec<_> = digits_le(n, radix).collect();
+                little_endian.reverse();
+                assert_eq!(little_endian, expected, "{n} {radix}");
+
+                assert_eq!(num_digits(n, radix) as usize, expected.len(), "{n} {radix}");
+                assert_eq!(digit_sum(n, radix), expected.iter().sum::<u32>(), "{n} {radix}");
+                assert_eq!(from_digits(expected, radix), Some(n), "{n} {radix}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_mixed_ends() {
+        let mut digits = digits_le(123_456u32, 10);
+
+        assert_eq!(digits.len(), 6);
+        assert_eq!(digits.next(), Some(6));
+        assert_eq!(digits.next_back(), Some(1));
+        assert_eq!(digits.next(), Some(5));
+        assert_eq!(digits.len(), 3);
+        assert_eq!(digits.rev().collect::<Vec<_>>(), [2, 3, 4]);
+    }
+
+    #[test]
+    fn test_powers_of_ten() {
+        // Floating point logarithms give the wrong count for some of these
+        for k in 0..20 {
+            let n = 10u64.pow(k);
+            assert_eq!(num_digits(n, 10), k + 1, "{n}");
+            assert_eq!(num_digits(n - 1, 10), k.max(1), "{n}");
+        }
+    }
+
+    #[test]
+    fn test_other_radices() {
+        assert_eq!(digits_be(35u8, 36).collect::<Vec<_>>(), [35]);
+        assert_eq!(digits_be(36i32, 36).collect::<Vec<_>>(), [1, 0]);
+        assert_eq!(digits_be(i64::MAX, 2).count(), 63);
+        assert_eq!(u32::from_str_radix("zz9", 36), Ok(from_digits([35, 35, 9], 36).unwrap()));
+        assert_eq!(num_digits(0u16, 7), 1);
+    }
+
+    #[test]
+    fn test_from_digits() {
+        assert_eq!(from_digits::<u32, _>([], 10), Some(0));
+        assert_eq!(from_digits::<u32, _>([0, 0, 4, 2], 10), Some(42));
+        assert_eq!(from_digits::<u8, _>([2, 5, 5], 10), Some(255));
+        assert_eq!(from_digits::<u8, _>([2, 5, 6], 10), None);
+        assert_eq!(from_digits::<i8, _>([1; 7], 2), Some(127));
+        assert_eq!(from_digits::<i8, _>([1; 8], 2), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a digit")]
+    fn test_from_digits_rejects_large_digit() {
+        from_digits::<u32, _>([1, 10], 10);
+    }
+
+    #[test]
+    fn test_digit_product() {
+        assert_eq!(digit_product(0u32, 10), 0);
+        assert_eq!(digit_product(7u32, 10), 7);
+        assert_eq!(digit_product(999u32, 10), 729);
+        assert_eq!(digit_product(1_234u32, 10), 24);
+        assert_eq!(digit_product(u128::MAX, 2), 1);
+        assert!((1..10_000u32).all(|n| digit_product(n, 10) <= n));
+    }
+
+    #[test]
+    fn test_reverse_digits() {
+        assert_eq!(reverse_digits(12_345u32, 10), Some(54_321));
+        assert_eq!(reverse_digits(1_200u32, 10), Some(21));
+        assert_eq!(reverse_digits(0u32, 10), Some(0));
+        assert_eq!(reverse_digits(0b1011u8, 2), Some(0b1101));
+        // 4000000009 fits in a u32, but 9000000004 doesn't
+        assert_eq!(reverse_digits(4_000_000_009u32, 10), None);
+        assert_eq!(reverse_digits(4_000_000_009u64, 10), Some(9_000_000_004));
+    }
+
+    #[test]
+    #[should_panic(expected = "radix must be in 2..=36")]
+    fn test_rejects_radix() {
+        num_digits(10u32, 37);
+    }
+}
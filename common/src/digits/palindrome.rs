@@ -0,0 +1,220 @@
+use crate::digits::Digits;
+use num::traits::PrimInt;
+use std::ops::{Bound, RangeBounds};
+
+/// Return if ``n`` reads the same forwards and backwards in ``radix``
+pub fn is_palindrome<T: PrimInt>(n: T, radix: u32) -> bool {
+    let mut digits = Digits::new(n, radix);
+
+    // Meet in the middle, which leaves the middle digit of an odd length unpaired
+    while let (Some(first), Some(last)) = (digits.next_back(), digits.next()) {
+        if first != last {
+            return false;
+        }
+    }
+    true
+}
+
+/// Iterator over the palindromes in a radix in ascending order, optionally limited to those with a
+/// given number of digits.
+///
+/// Each palindrome is built from its first half, so the palindromes with ``k`` digits come from
+/// counting through the ``(k + 1) / 2`` digit halves in order, and no other integers are ever
+/// looked at. Stops once the palindromes no longer fit in ``T``.
+pub struct Palindromes<T> {
+    radix: T,
+    /// The number of digits of the palindromes being generated
+    num_digits: u32,
+    max_digits: u32,
+    /// The first half of the next palindrome, middle digit included
+    half: T,
+    /// One past the last first half with the current number of digits
+    half_end: T,
+    finished: bool,
+}
+
+impl<T: PrimInt> Palindromes<T> {
+    /// Every palindrome in ``radix``, starting from 0
+    pub fn new(radix: u32) -> Palindromes<T> {
+        Palindromes::with_num_digits(radix, ..)
+    }
+
+    /// The palindromes in ``radix`` whose number of digits is in ``range``
+    ///
+    /// 0 is the only palindrome counted as having a single digit without being positive.
+    pub fn with_num_digits<R: RangeBounds<u32>>(radix: u32, range: R) -> Palindromes<T> {
+        let min_digits = match range.start_bound() {
+            Bound::Included(&min) => min,
+            Bound::Excluded(&min) => min.saturating_add(1),
+            Bound::Unbounded => 1,
+        };
+        let max_digits = match range.end_bound() {
+            Bound::Included(&max) => max,
+            Bound::Excluded(&max) => max.saturating_sub(1),
+            Bound::Unbounded => u32::MAX,
+        };
+
+        let mut palindromes = Palindromes {
+            radix: super::to_radix(radix),
+            num_digits: 0,
+            max_digits,
+            half: T::zero(),
+            half_end: T::zero(),
+            finished: false,
+        };
+        palindromes.start_length(min_digits.max(1));
+        palindromes
+    }
+
+    /// Move on to the palindromes with ``num_digits`` digits
+    fn start_length(&mut self, num_digits: u32) {
+        let half_digits = num_digits.div_ceil(2);
+        // Every palindrome with this many digits is at least radix^(num_digits - 1)
+        let smallest = checked_power(self.radix, num_digits - 1);
+
+        match (smallest, checked_power(self.radix, half_digits)) {
+            (Some(_), Some(half_end)) if num_digits <= self.max_digits => {
+                self.num_digits = num_digits;
+                self.half = if num_digits == 1 {
+                    T::zero()
+                } else {
+                    half_end / self.radix
+                };
+                self.half_end = half_end;
+            }
+            _ => self.finished = true,
+        }
+    }
+
+    /// The palindrome with the given first half, or ``None`` if it overflows
+    fn mirror(&self, half: T) -> Option<T> {
+        let mut palindrome = half;
+        // The middle digit of an odd length is only used once
+        let mut rest = if self.num_digits % 2 == 1 {
+            half / self.radix
+        } else {
+            half
+        };
+
+        while rest > T::zero() {
+            palindrome = palindrome
+                .checked_mul(&self.radix)?
+                .checked_add(&(rest % self.radix))?;
+            rest = rest / self.radix;
+        }
+        Some(palindrome)
+    }
+}
+
+impl<T: PrimInt> Iterator for Palindromes<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        while !self.finished {
+            if self.half < self.half_end {
+                let palindrome = self.mirror(self.half);
+                self.half = self.half + T::one();
+
+                if palindrome.is_none() {
+                    // The rest of this length, and every longer palindrome, overflows as well
+                    self.finished = true;
+                }
+                return palindrome;
+            }
+
+            match self.num_digits.checked_add(1) {
+                Some(num_digits) => self.start_length(num_digits),
+                None => self.finished = true,
+            }
+        }
+        None
+    }
+}
+
+fn checked_power<T: PrimInt>(base: T, exponent: u32) -> Option<T> {
+    (0..exponent).try_fold(T::one(), |power, _| power.checked_mul(&base))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_is_palindrome() {
+        assert!(is_palindrome(0u32, 10));
+        assert!(is_palindrome(7u32, 10));
+        assert!(is_palindrome(12_321u32, 10));
+        assert!(is_palindrome(1_221u32, 10));
+        assert!(!is_palindrome(1_231u32, 10));
+        assert!(!is_palindrome(10u32, 10));
+
+        // 585 is 1001001001 in binary
+        assert!(is_palindrome(585u32, 2));
+        assert!(is_palindrome(u128::MAX, 2));
+        assert!(!is_palindrome(u128::MAX - 1, 2));
+        assert!(is_palindrome(0xabbau64, 16));
+    }
+
+    #[test]
+    fn test_matches_filter() {
+        for radix in [2, 3, 10, 16] {
+            let expected: Vec<_> = (0..100_000u64).filter(|&n| is_palindrome(n, radix)).collect();
+            let generated: Vec<_> = Palindromes::<u64>::new(radix)
+                .take_while(|&n| n < 100_000)
+                .collect();
+
+            assert_eq!(generated, expected, "{radix}");
+        }
+    }
+
+    #[test]
+    fn test_num_digits() {
+        let three_digits: Vec<u32> = Palindromes::with_num_digits(10, 3..=3).collect();
+        assert_eq!(three_digits.len(), 90);
+        assert_eq!(three_digits[..3], [101, 111, 121]);
+        assert_eq!(three_digits.last(), Some(&999));
+
+        let short: Vec<u32> = Palindromes::with_num_digits(10, ..3).collect();
+        assert_eq!(short.len(), 19);
+        assert_eq!(short[9..11], [9, 11]);
+
+        let even: Vec<u32> = Palindromes::with_num_digits(2, 4..=4).collect();
+        assert_eq!(even, [0b1001, 0b1111]);
+
+        assert_eq!(Palindromes::<u32>::with_num_digits(10, 4..4).count(), 0);
+    }
+
+    #[test]
+    fn test_stops_at_overflow() {
+        // 252 is the largest palindrome in a u8, and 262 would overflow
+        let all: Vec<u8> = Palindromes::new(10).collect();
+        assert_eq!(all.len(), 10 + 9 + 16);
+        assert_eq!(all.last(), Some(&252));
+
+        let mut widest = Palindromes::<u128>::with_num_digits(2, 128..);
+        assert_eq!(widest.next(), Some((1 << 127) + 1));
+        assert_eq!(widest.next(), Some((1 << 127) + (1 << 63) + (1 << 64) + 1));
+        assert_eq!(Palindromes::<u128>::with_num_digits(2, 129..).next(), None);
+
+        // 4294994924 is just past u32::MAX
+        assert_eq!(Palindromes::<u32>::new(10).last(), Some(4_294_884_924));
+        assert_eq!(Palindromes::<u64>::with_num_digits(10, 21..).next(), None);
+    }
+
+    #[test]
+    fn test_double_base_palindromes() {
+        // Problem 36: the numbers below a million that are palindromes in base 10 and base 2
+        let sum: u64 = Palindromes::<u64>::new(10)
+            .take_while(|&n| n < 1_000_000)
+            .filter(|&n| is_palindrome(n, 2))
+            .sum();
+        assert_eq!(sum, 872_187);
+
+        // Generating the binary palindromes instead gives the same numbers
+        let sum: u128 = Palindromes::<u128>::new(2)
+            .take_while(|&n| n < 1_000_000)
+            .filter(|&n| is_palindrome(n, 10))
+            .sum();
+        assert_eq!(sum, 872_187);
+    }
+}
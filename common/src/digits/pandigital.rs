@@ -0,0 +1,213 @@
+use crate::digits::{digits_be, digits_le, from_digits};
+use num::traits::PrimInt;
+use std::ops::RangeInclusive;
+
+/// Return if the decimal digits of ``n`` are exactly the digits in ``digits``, each used once
+///
+/// The usual ranges are ``1..=9``, ``0..=9`` and ``1..=k``.
+pub fn is_pandigital<T: PrimInt>(n: T, digits: RangeInclusive<u32>) -> bool {
+    is_pandigital_concat(&[n], digits)
+}
+
+/// Return if writing ``parts`` one after the other gives a pandigital number
+///
+/// This is the check for pandigital identities like 39 × 186 = 7254 in problem 32, which pass
+/// all three numbers at once.
+pub fn is_pandigital_concat<T: PrimInt>(parts: &[T], digits: RangeInclusive<u32>) -> bool {
+    assert!(*digits.end() <= 9, "pandigital digits must be in 0..=9");
+
+    // A bit for each digit seen so far
+    let mut seen = 0u32;
+    for &part in parts {
+        for digit in digits_le(part, 10) {
+            if !digits.contains(&digit) || seen & (1 << digit) != 0 {
+                return false;
+            }
+            seen |= 1 << digit;
+        }
+    }
+
+    seen == digits.fold(0, |mask, digit| mask | (1 << digit))
+}
+
+/// The concatenated product of ``n`` and (1, 2, ..., ``k``), or ``None`` if it overflows
+///
+/// 192 and (1, 2, 3) give 192384576, by writing out 192, 384 and 576 in turn.
+pub fn concatenated_product(n: u64, k: u64) -> Option<u64> {
+    let products = (1..=k).map(|i| n.checked_mul(i)).collect::<Option<Vec<_>>>()?;
+    from_digits(products.into_iter().flat_map(|product| digits_be(product, 10)), 10)
+}
+
+/// Iterator over the pandigital numbers for a range of digits, in ascending or descending order.
+///
+/// Every pandigital number has the same number of digits, so numeric order is the lexicographic
+/// order of the digit permutations, and each step is the next permutation in that order. Numbers
+/// with a leading zero are skipped. Being lazy, searches like "the largest pandigital prime" can
+/// go from the largest candidate down and stop at the first match with ``find``.
+pub struct Pandigitals {
+    /// The digits of the next number, most significant first
+    digits: Vec<u32>,
+    ascending: bool,
+    finished: bool,
+}
+
+impl Pandigitals {
+    /// The pandigital numbers for ``digits`` from the smallest up
+    pub fn ascending(digits: RangeInclusive<u32>) -> Pandigitals {
+        assert!(*digits.end() <= 9, "pandigital digits must be in 0..=9");
+        let mut digits: Vec<_> = digits.collect();
+        // Skip past the leading zero, to 1023456789 for the full range
+        if digits.len() > 1 && digits[0] == 0 {
+            digits.swap(0, 1);
+        }
+
+        Pandigitals {
+            finished: digits.is_empty(),
+            digits,
+            ascending: true,
+        }
+    }
+
+    /// The pandigital numbers for ``digits`` from the largest down
+    pub fn descending(digits: RangeInclusive<u32>) -> Pandigitals {
+        assert!(*digits.end() <= 9, "pandigital digits must be in 0..=9");
+        let digits: Vec<_> = digits.rev().collect();
+
+        Pandigitals {
+            finished: digits.is_empty(),
+            digits,
+            ascending: false,
+        }
+    }
+
+    /// Step to the next permutation in order, returning false if there isn't one
+    fn step(&mut self) -> bool {
+        let digits = &mut self.digits;
+        let in_order = |a: u32, b: u32| if self.ascending { a < b } else { a > b };
+
+        // The suffix after ``pivot`` is as far along as it can go, so the pivot has to move on
+        let pivot = (1..digits.len())
+            .rev()
+            .find(|&i| in_order(digits[i - 1], digits[i]));
+        let Some(pivot) = pivot.map(|i| i - 1) else {
+            return false;
+        };
+        let swap = (pivot + 1..digits.len())
+            .rev()
+            .find(|&i| in_order(digits[pivot], digits[i]))
+            .unwrap();
+
+        digits.swap(pivot, swap);
+        digits[pivot + 1..].reverse();
+        true
+    }
+}
+
+impl Iterator for Pandigitals {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        if self.finished {
+            return None;
+        }
+
+        let n = from_digits(self.digits.iter().copied(), 10).unwrap();
+        // Descending, a leading zero means every number left has one
+        self.finished = !self.step() || (self.digits.len() > 1 && self.digits[0] == 0);
+        Some(n)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::is_prime;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_is_pandigital() {
+        assert!(is_pandigital(123_456_789u32, 1..=9));
+        assert!(is_pandigital(918_273_645u64, 1..=9));
+        assert!(is_pandigital(1_406_357_289u64, 0..=9));
+        assert!(is_pandigital(2_143u32, 1..=4));
+
+        // Missing, repeated and out of range digits
+        assert!(!is_pandigital(12_345_678u32, 1..=9));
+        assert!(!is_pandigital(112_345_678u32, 1..=9));
+        assert!(!is_pandigital(1_023_456_789u64, 1..=9));
+        assert!(!is_pandigital(2_153u32, 1..=4));
+    }
+
+    #[test]
+    fn test_pandigital_products() {
+        // Problem 32: the products whose identity a × b = c uses the digits 1 to 9 once
+        let mut products = HashSet::new();
+        for a in 1..100u32 {
+            for b in a + 1..=9_999 / a {
+                if is_pandigital_concat(&[a, b, a * b], 1..=9) {
+                    products.insert(a * b);
+                }
+            }
+        }
+
+        assert!(products.contains(&7_254));
+        assert_eq!(products.iter().sum::<u32>(), 45_228);
+    }
+
+    #[test]
+    fn test_concatenated_product() {
+        assert_eq!(concatenated_product(192, 3), Some(192_384_576));
+        assert_eq!(concatenated_product(9, 5), Some(918_273_645));
+        assert_eq!(concatenated_product(u64::MAX / 2, 3), None);
+
+        // Problem 38: the largest pandigital concatenated product with n > 1
+        let largest = (1..10_000)
+            .flat_map(|x| (2..=9).filter_map(move |k| concatenated_product(x, k)))
+            .filter(|&product| is_pandigital(product, 1..=9))
+            .max();
+        assert_eq!(largest, Some(932_718_654));
+    }
+
+    #[test]
+    fn test_matches_filter() {
+        for k in 1..=6 {
+            let smallest = 10u64.pow(k - 1);
+            let expected: Vec<_> = (smallest..smallest * 10)
+                .filter(|&n| is_pandigital(n, 1..=k))
+                .collect();
+
+            assert_eq!(Pandigitals::ascending(1..=k).collect::<Vec<_>>(), expected, "{k}");
+
+            let mut descending: Vec<_> = Pandigitals::descending(1..=k).collect();
+            descending.reverse();
+            assert_eq!(descending, expected, "{k}");
+        }
+    }
+
+    #[test]
+    fn test_leading_zero() {
+        let ascending: Vec<_> = Pandigitals::ascending(0..=3).collect();
+        let descending: Vec<_> = Pandigitals::descending(0..=3).collect();
+
+        // 4! orderings, less the 3! with a leading zero
+        assert_eq!(ascending.len(), 18);
+        assert_eq!(ascending[..3], [1_023, 1_032, 1_203]);
+        assert_eq!(descending[..2], [3_210, 3_201]);
+        assert_eq!(descending.last(), Some(&1_023));
+        assert!(ascending.iter().rev().eq(descending.iter()));
+
+        assert_eq!(Pandigitals::ascending(0..=9).next(), Some(1_023_456_789));
+        assert_eq!(Pandigitals::ascending(0..=0).collect::<Vec<_>>(), [0]);
+    }
+
+    #[test]
+    fn test_largest_pandigital_prime() {
+        // Every 8 and 9 digit pandigital number is divisible by 3, so the search runs through them
+        // before stopping early among the 7 digit ones
+        let largest = (1..=9)
+            .rev()
+            .find_map(|k| Pandigitals::descending(1..=k).find(|&n| is_prime(n)));
+
+        assert_eq!(largest, Some(7_652_413));
+    }
+}
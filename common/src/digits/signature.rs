@@ -0,0 +1,262 @@
+use crate::digits::digits_le;
+use num::traits::PrimInt;
+use std::collections::HashMap;
+
+/// The largest radix the digit functions accept
+const MAX_RADIX: usize = 36;
+
+/// Bits in each word of the packed signature
+const WORD_BITS: u32 = u64::BITS;
+
+/// The top bits of the packed signature hold the radix, which is at most 36
+const RADIX_BITS: u32 = 6;
+
+/// The bits below the radix, shared out evenly between the digit counts
+const COUNT_BITS: u32 = 3 * WORD_BITS - RADIX_BITS;
+
+/// The multiset of digits of an integer, which is equal for two integers exactly when their digits
+/// are permutations of each other.
+///
+/// The radix and a count for each digit value are packed into three ``u64`` words, so it's a 24
+/// byte ``Copy`` value that is cheap to hash and compare, and can key the map when grouping
+/// permutations together. The order is only there for ordered maps, and has no meaning beyond that.
+///
+/// Each count gets an equal share of the bits, up to 8. Even in radix 36 that leaves 5 bits a
+/// count, enough for all 25 digits of a ``u128``, so every integer in every radix has a signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DigitSignature {
+    /// The count of the digit ``d`` is in the ``field_width(radix)`` bits from
+    /// ``d * field_width(radix)``, with the radix itself in the top ``RADIX_BITS``
+    packed: [u64; 3],
+}
+
+impl DigitSignature {
+    /// The signature of the digits of ``n`` in ``radix``
+    ///
+    /// Leading zeros aren't significant, so 102 and 21 have different signatures. Panics if ``n``
+    /// is negative or the radix is outside 2..=36.
+    pub fn new<T: PrimInt>(n: T, radix: u32) -> DigitSignature {
+        DigitSignature::pack(radix, &digit_counts(n, radix))
+    }
+
+    /// The signature of ``n`` padded with leading zeros to ``width`` digits
+    ///
+    /// This makes the leading zeros significant, so 102, 210 and 21 (as 021) all have the same
+    /// signature for a width of 3. Panics if ``n`` has more than ``width`` digits, or there are
+    /// too many zeros to pack, which is over 255 up to radix 23 and over 31 in radix 36.
+    pub fn with_width<T: PrimInt>(n: T, radix: u32, width: u32) -> DigitSignature {
+        let mut counts = digit_counts(n, radix);
+        let num_digits: u32 = counts.iter().sum();
+        assert!(num_digits <= width, "{num_digits} digits don't fit in a width of {width}");
+
+        counts[0] += width - num_digits;
+        DigitSignature::pack(radix, &counts)
+    }
+
+    fn pack(radix: u32, counts: &[u32; MAX_RADIX]) -> DigitSignature {
+        let width = field_width(radix);
+        let mut signature = DigitSignature { packed: [0; 3] };
+        signature.set_field(COUNT_BITS, RADIX_BITS, radix);
+
+        for (digit, &count) in counts.iter().enumerate().take(radix as usize) {
+            assert!(
+                count < 1 << width,
+                "{count} of the digit {digit} don't fit in a signature in radix {radix}"
+            );
+            signature.set_field(digit as u32 * width, width, count);
+        }
+
+        signature
+    }
+
+    /// Write ``value`` to the empty field of ``width`` bits from bit ``offset``
+    fn set_field(&mut self, offset: u32, width: u32, value: u32) {
+        let (word, shift) = ((offset / WORD_BITS) as usize, offset % WORD_BITS);
+        self.packed[word] |= (value as u64) << shift;
+
+        // The field may carry over into the next word
+        if shift + width > WORD_BITS {
+            self.packed[word + 1] |= (value as u64) >> (WORD_BITS - shift);
+        }
+    }
+
+    /// Read the field of ``width`` bits from bit ``offset``
+    fn field(&self, offset: u32, width: u32) -> u32 {
+        let (word, shift) = ((offset / WORD_BITS) as usize, offset % WORD_BITS);
+        let mut bits = self.packed[word] >> shift;
+
+        if shift + width > WORD_BITS {
+            bits |= self.packed[word + 1] << (WORD_BITS - shift);
+        }
+        (bits & ((1 << width) - 1)) as u32
+    }
+
+    pub fn radix(&self) -> u32 {
+        self.field(COUNT_BITS, RADIX_BITS)
+    }
+
+    /// The number of times ``digit`` appears
+    pub fn count(&self, digit: u32) -> u32 {
+        let radix = self.radix();
+        assert!(digit < radix, "{digit} is not a digit in radix {radix}");
+
+        let width = field_width(radix);
+        self.field(digit * width, width)
+    }
+
+    /// The total number of digits, including any padding
+    pub fn num_digits(&self) -> u32 {
+        (0..self.radix()).map(|digit| self.count(digit)).sum()
+    }
+}
+
+/// The number of bits given to each digit count in ``radix``
+fn field_width(radix: u32) -> u32 {
+    (COUNT_BITS / radix).min(8)
+}
+
+/// ``counts[d]`` is the number of times the digit ``d`` appears in ``n``
+fn digit_counts<T: PrimInt>(n: T, radix: u32) -> [u32; MAX_RADIX] {
+    let mut counts = [0; MAX_RADIX];
+    for digit in digits_le(n, radix) {
+        counts[digit as usize] += 1;
+    }
+    counts
+}
+
+/// Group ``numbers`` by the signature of their digits, keeping each group in the order given
+pub fn group_by_signature<T, I>(numbers: I, radix: u32) -> HashMap<DigitSignature, Vec<T>>
+where
+    T: PrimInt,
+    I: IntoIterator<Item = T>,
+{
+    group_by(numbers, |n| DigitSignature::new(n, radix))
+}
+
+/// Group ``numbers`` by the signature of their digits when padded to ``width`` digits
+pub fn group_by_padded_signature<T, I>(
+    numbers: I,
+    radix: u32,
+    width: u32,
+) -> HashMap<DigitSignature, Vec<T>>
+where
+    T: PrimInt,
+    I: IntoIterator<Item = T>,
+{
+    group_by(numbers, |n| DigitSignature::with_width(n, radix, width))
+}
+
+fn group_by<T, I, F>(numbers: I, signature: F) -> HashMap<DigitSignature, Vec<T>>
+where
+    T: PrimInt,
+    I: IntoIterator<Item = T>,
+    F: Fn(T) -> DigitSignature,
+{
+    let mut groups: HashMap<_, Vec<T>> = HashMap::new();
+    for n in numbers {
+        groups.entry(signature(n)).or_default().push(n);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::get_digits_sorted;
+
+    #[test]
+    fn test_matches_sorted_digits() {
+        let keys: Vec<_> = (1..1_500u32)
+            .map(|n| (n, DigitSignature::new(n, 10), get_digits_sorted(n)))
+            .collect();
+
+        for (a, signature_a, sorted_a) in &keys {
+            for (b, signature_b, sorted_b) in &keys {
+                assert_eq!(signature_a == signature_b, sorted_a == sorted_b, "{a} {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_leading_zeros() {
+        let signature = |n: u32| DigitSignature::new(n, 10);
+        assert_ne!(signature(102), signature(21));
+        assert_eq!(signature(102), signature(120));
+
+        let padded = |n: u32| DigitSignature::with_width(n, 10, 3);
+        assert_eq!(padded(102), padded(21));
+        assert_eq!(padded(210), padded(21));
+        assert_eq!(padded(0).count(0), 3);
+        assert_eq!(padded(21).num_digits(), 3);
+        assert_eq!(padded(21).count(0), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "don't fit in a width of 3")]
+    fn test_width_too_small() {
+        DigitSignature::with_width(1_234u32, 10, 3);
+    }
+
+    #[test]
+    fn test_radix() {
+        // 5 is 101 in binary, rather than the single digit 5
+        assert_ne!(DigitSignature::new(5u32, 2), DigitSignature::new(5u32, 10));
+        assert_eq!(DigitSignature::new(0b1100u8, 2), DigitSignature::new(0b1010u8, 2));
+        assert_eq!(DigitSignature::new(0xfa, 16), DigitSignature::new(0xafu64, 16));
+
+        let max = DigitSignature::new(u128::MAX, 2);
+        assert_eq!(max.count(1), 128);
+        assert_eq!(max.count(0), 0);
+        assert_eq!(max.radix(), 2);
+    }
+
+    #[test]
+    fn test_packed() {
+        assert_eq!(std::mem::size_of::<DigitSignature>(), 24);
+
+        let padded = DigitSignature::with_width(1u8, 10, 255);
+        assert_eq!(padded.count(0), 254);
+        assert_eq!(padded.count(1), 1);
+    }
+
+    #[test]
+    fn test_counts_round_trip() {
+        // The counts read back out match the digits in every radix, including the widest counts
+        let numbers = [0, 1, 36u128.pow(8), 36u128.pow(24), u128::MAX, u128::MAX / 3, 1 << 127];
+
+        for radix in 2..=36 {
+            for n in numbers {
+                let signature = DigitSignature::new(n, radix);
+                let counts = digit_counts(n, radix);
+
+                assert_eq!(signature.radix(), radix);
+                for digit in 0..radix {
+                    assert_eq!(signature.count(digit), counts[digit as usize], "{n} {radix}");
+                }
+            }
+        }
+
+        // 36^24 has 24 zeros, and 25 digits in all
+        let signature = DigitSignature::new(36u128.pow(24), 36);
+        assert_eq!(signature.count(0), 24);
+        assert_eq!(signature.num_digits(), 25);
+        assert_ne!(signature, DigitSignature::new(36u128.pow(23), 36));
+    }
+
+    #[test]
+    fn test_group_by_signature() {
+        // Problem 49: the four digit primes in arithmetic sequence share their digits
+        let groups = group_by_signature([1_487u32, 1_234, 4_817, 8_147, 4_321], 10);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[&DigitSignature::new(1_487u32, 10)], [1_487, 4_817, 8_147]);
+
+        // Problem 62: the three cube permutations
+        let cubes = [345u128, 384, 405].map(|n| n.pow(3));
+        assert_eq!(group_by_signature(cubes, 10).len(), 1);
+
+        // As four digits, 0012, 0102, 0210 and 1020 are all permutations
+        let padded = group_by_padded_signature([12u64, 102, 210, 1_020, 1_234], 10, 4);
+        assert_eq!(padded.len(), 2);
+        assert_eq!(padded[&DigitSignature::with_width(12u64, 10, 4)], [12, 102, 210, 1_020]);
+    }
+}
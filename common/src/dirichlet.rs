@@ -0,0 +1,329 @@
+use num::traits::{Num, Zero};
+use std::ops::{Index, Mul};
+
+/// A function on the positive integers, given either as a ``Table`` or as a closure over ``u64``
+pub trait ArithmeticFunction<T> {
+    fn at(&self, n: u64) -> T;
+}
+
+impl<T, F: Fn(u64) -> T> ArithmeticFunction<T> for F {
+    fn at(&self, n: u64) -> T {
+        self(n)
+    }
+}
+
+/// The values of an arithmetic function for every ``n`` up to a limit, indexed by ``n``
+///
+/// The entry at 0 isn't part of the function, and is only there so that ``table[n]`` is f(n).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table<T> {
+    values: Vec<T>,
+}
+
+impl<T: Copy> Table<T> {
+    /// Wrap the values of a function, with ``values[n]`` holding f(n)
+    pub fn new(values: Vec<T>) -> Table<T> {
+        assert!(!values.is_empty(), "a table needs an entry for 0");
+        Table { values }
+    }
+
+    /// Tabulate ``f`` for ``1 <= n <= limit``, with the entry at 0 set to zero
+    pub fn from_fn<F: ArithmeticFunction<T>>(limit: u64, f: &F) -> Table<T>
+    where
+        T: Zero,
+    {
+        let values = std::iter::once(T::zero())
+            .chain((1..=limit).map(|n| f.at(n)))
+            .collect();
+        Table { values }
+    }
+
+    /// The largest ``n`` in the table
+    pub fn limit(&self) -> u64 {
+        (self.values.len() - 1) as u64
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+}
+
+impl<T: Copy> ArithmeticFunction<T> for Table<T> {
+    fn at(&self, n: u64) -> T {
+        self.values[n as usize]
+    }
+}
+
+impl<T> Index<usize> for Table<T> {
+    type Output = T;
+
+    fn index(&self, n: usize) -> &T {
+        &self.values[n]
+    }
+}
+
+/// The Dirichlet convolution ``(f * g)(n)``, the sum of ``f(d) g(n / d)`` over the divisors ``d``
+/// of ``n``, for every ``n <= limit``
+///
+/// Each pair ``d``, ``m`` with ``d * m <= limit`` is visited once, taking O(limit log limit) time.
+pub fn convolve<T, F, G>(f: &F, g: &G, limit: u64) -> Table<T>
+where
+    T: Copy + Zero + Mul<Output = T>,
+    F: ArithmeticFunction<T>,
+    G: ArithmeticFunction<T>,
+{
+    let g_values = Table::from_fn(limit, g);
+    let mut values = vec![T::zero(); limit as usize + 1];
+
+    for d in 1..=limit {
+        let f_d = f.at(d);
+        for m in 1..=limit / d {
+            let n = (d * m) as usize;
+            values[n] = values[n] + f_d * g_values[m as usize];
+        }
+    }
+
+    Table { values }
+}
+
+/// The Dirichlet inverse of ``f`` for every ``n <= limit``, the ``g`` with ``f * g`` equal to 1
+/// at 1 and 0 elsewhere
+///
+/// ``f(1)`` must divide 1, and the values are generally negative, so ``T`` should be signed.
+/// Each ``g(n)`` is fixed once every smaller divisor's term has been added to it, so the terms are
+/// pushed forward to the multiples in O(limit log limit) time.
+pub fn inverse<T, F>(f: &F, limit: u64) -> Table<T>
+where
+    T: Copy + Num,
+    F: ArithmeticFunction<T>,
+{
+    let f_values = Table::from_fn(limit, f);
+    let f_1 = f.at(1);
+    assert!(
+        !f_1.is_zero(),
+        "a function with f(1) = 0 has no Dirichlet inverse"
+    );
+
+    // Holds the sum of f(d) g(n / d) over d > 1 until g(n) is known
+    let mut values = vec![T::zero(); limit as usize + 1];
+
+    for m in 1..=limit {
+        let m_index = m as usize;
+        values[m_index] = if m == 1 {
+            T::one() / f_1
+        } else {
+            (T::zero() - values[m_index]) / f_1
+        };
+
+        let g_m = values[m_index];
+        for d in 2..=limit / m {
+            let n = (d * m) as usize;
+            values[n] = values[n] + f_values[d as usize] * g_m;
+        }
+    }
+
+    Table { values }
+}
+
+/// A run of ``i`` with the same ``n / i``
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FloorBlock {
+    /// The first ``i`` in the run
+    pub start: u64,
+    /// The last ``i`` in the run, inclusive
+    pub end: u64,
+    /// ``n / i`` for every ``i`` in the run
+    pub quotient: u64,
+}
+
+/// Iterator over the runs of ``i`` with the same ``n / i``, for ``start <= i <= n``.
+///
+/// ``n / i`` only takes O(sqrt(n)) distinct values, so any sum over ``i`` that only depends on
+/// ``i`` through ``n / i`` (and a cheap sum over each run) can be done in O(sqrt(n)) steps. This
+/// is the basis of the hyperbola method and of the summatory function recurrences.
+pub struct FloorBlocks {
+    n: u64,
+    next: u64,
+}
+
+impl FloorBlocks {
+    /// The runs covering ``1 <= i <= n``
+    pub fn new(n: u64) -> FloorBlocks {
+        FloorBlocks::starting_at(n, 1)
+    }
+
+    /// The runs covering ``start <= i <= n``
+    pub fn starting_at(n: u64, start: u64) -> FloorBlocks {
+        FloorBlocks {
+            n,
+            next: start.max(1),
+        }
+    }
+}
+
+impl Iterator for FloorBlocks {
+    type Item = FloorBlock;
+
+    fn next(&mut self) -> Option<FloorBlock> {
+        if self.next > self.n {
+            return None;
+        }
+
+        let start = self.next;
+        let quotient = self.n / start;
+        let end = self.n / quotient;
+        match end.checked_add(1) {
+            Some(next) => self.next = next,
+            // Only when n = u64::MAX, and this is the final run
+            None => self.n = 0,
+        }
+
+        Some(FloorBlock {
+            start,
+            end,
+            quotient,
+        })
+    }
+}
+
+/// The sum of ``(f * g)(n)`` for ``n <= x`` by the Dirichlet hyperbola method
+///
+/// Every term ``f(a) g(b)`` with ``ab <= x`` has ``a <= sqrt(x)`` or ``b <= sqrt(x)``, so the sum
+/// is ``sum(f(a) G(x / a)) + sum(g(b) F(x / b)) - F(sqrt(x)) G(sqrt(x))`` with ``a`` and ``b``
+/// up to ``sqrt(x)``, where ``F`` and ``G`` are the summatory functions of ``f`` and ``g``. Given
+/// closed forms for those, this takes O(sqrt(x)) time.
+pub fn hyperbola_sum<F, G, SF, SG>(x: u64, f: &F, g: &G, sum_f: SF, sum_g: SG) -> i128
+where
+    F: ArithmeticFunction<i128>,
+    G: ArithmeticFunction<i128>,
+    SF: Fn(u64) -> i128,
+    SG: Fn(u64) -> i128,
+{
+    let root = x.isqrt();
+
+    let left: i128 = (1..=root).map(|a| f.at(a) * sum_g(x / a)).sum();
+    let right: i128 = (1..=root).map(|b| g.at(b) * sum_f(x / b)).sum();
+
+    left + right - sum_f(root) * sum_g(root)
+}
+
+/// The sum of τ(n), the number of divisors, for ``n <= x``
+///
+/// τ is ``1 * 1``, so this is ``hyperbola_sum`` with ``F(v) = G(v) = v``.
+pub fn divisor_count_summatory(x: u64) -> u128 {
+    let total = hyperbola_sum(x, &|_| 1, &|_| 1, |v| v as i128, |v| v as i128);
+    total as u128
+}
+
+/// The sum of σ(n), the sum of divisors, for ``n <= x``
+///
+/// σ is ``id * 1``, so this is ``hyperbola_sum`` with ``F(v) = v(v + 1) / 2`` and ``G(v) = v``.
+/// Takes O(sqrt(x)) time, a few milliseconds for 10^12.
+pub fn divisor_sum_summatory(x: u64) -> u128 {
+    let triangle = |v: u64| v as i128 * (v as i128 + 1) / 2;
+    let total = hyperbola_sum(x, &|a| a as i128, &|_| 1, triangle, |v| v as i128);
+    total as u128
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::arith::linear_sieve::ArithmeticTables;
+
+    const LIMIT: u64 = 5_000;
+
+    #[test]
+    fn test_convolve_identities() {
+        let tables = ArithmeticTables::new(LIMIT as usize, 1);
+        let one = |_| 1i64;
+        let identity = |n| n as i64;
+        let mobius = |n| tables.mobius(n as usize) as i64;
+        let totient = |n| tables.totient(n as usize) as i64;
+
+        let unit = convolve(&mobius, &one, LIMIT);
+        let id = convolve(&totient, &one, LIMIT);
+        let tau = convolve(&one, &one, LIMIT);
+        let sigma = convolve(&identity, &one, LIMIT);
+
+        for n in 1..=LIMIT {
+            let i = n as usize;
+            assert_eq!(unit[i], (n == 1) as i64, "{n}");
+            assert_eq!(id[i], n as i64, "{n}");
+            assert_eq!(tau[i], tables.num_divisors(i) as i64, "{n}");
+            assert_eq!(sigma[i], tables.divisor_sigma(i) as i64, "{n}");
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        let tables = ArithmeticTables::new(LIMIT as usize, 1);
+
+        // μ and 1 are each other's inverses
+        let mobius = inverse(&|_| 1i64, LIMIT);
+        let one = inverse(&mobius, LIMIT);
+        for n in 1..=LIMIT {
+            assert_eq!(mobius[n as usize], tables.mobius(n as usize) as i64, "{n}");
+            assert_eq!(one[n as usize], 1, "{n}");
+        }
+
+        // Convolving a table with its inverse gives the unit
+        let sigma = Table::from_fn(LIMIT, &|n| tables.divisor_sigma(n as usize) as i64);
+        let unit = convolve(&sigma, &inverse(&sigma, LIMIT), LIMIT);
+        assert!((1..=LIMIT).all(|n| unit[n as usize] == (n == 1) as i64));
+    }
+
+    #[test]
+    fn test_floor_blocks() {
+        for n in (0..300).chain([1_000_000, 999_983]) {
+            let mut expected = 1;
+            for block in FloorBlocks::new(n) {
+                assert_eq!(block.start, expected);
+                assert!((block.start..=block.end).all(|i| n / i == block.quotient));
+                expected = block.end + 1;
+            }
+            assert_eq!(expected, n + 1, "{n}");
+            assert!(FloorBlocks::new(n).count() as u64 <= 2 * n.isqrt());
+        }
+
+        let blocks: Vec<_> = FloorBlocks::starting_at(10, 2)
+            .map(|block| (block.start, block.end, block.quotient))
+            .collect();
+        assert_eq!(blocks, [(2, 2, 5), (3, 3, 3), (4, 5, 2), (6, 10, 1)]);
+
+        // The final run ends at u64::MAX without the next start overflowing
+        let half = u64::MAX / 2 + 1;
+        let blocks: Vec<_> = FloorBlocks::starting_at(u64::MAX, half / 2)
+            .map(|block| (block.start, block.end, block.quotient))
+            .collect();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[2], (half, u64::MAX, 1));
+    }
+
+    #[test]
+    fn test_summatory() {
+        let tables = ArithmeticTables::new(LIMIT as usize, 1);
+        let (mut tau, mut sigma) = (0, 0);
+
+        for x in 0..=LIMIT {
+            tau += tables.num_divisors(x as usize) as u128;
+            sigma += tables.divisor_sigma(x as usize) as u128;
+
+            assert_eq!(divisor_count_summatory(x), tau, "{x}");
+            assert_eq!(divisor_sum_summatory(x), sigma, "{x}");
+        }
+    }
+
+    #[test]
+    fn test_summatory_blocks_agree() {
+        // The sum of σ(n) is also the sum of d * floor(x / d), which the blocks give directly
+        let x = 1_000_000_000_000;
+        let by_blocks: u128 = FloorBlocks::new(x)
+            .map(|block| {
+                let (start, end) = (block.start as u128, block.end as u128);
+                (start + end) * (end - start + 1) / 2 * block.quotient as u128
+            })
+            .sum();
+
+        assert_eq!(divisor_sum_summatory(x), by_blocks);
+    }
+}
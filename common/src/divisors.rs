@@ -0,0 +1,211 @@
+use crate::factor::Factorization;
+use num::traits::PrimInt;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::ops::{Bound, RangeBounds};
+
+/// Lazy iterator over the divisors of an integer in ascending order, built from its factorisation.
+///
+/// Every divisor has a single parent: itself with one factor of its largest prime removed. A
+/// min-heap holds the frontier, and popping a divisor pushes at most three successors: one more
+/// power of its largest prime, the next prime on top of it, and its "sibling" with the largest
+/// prime swapped for the next one. Each divisor is pushed exactly once, and every successor is
+/// larger than its parent, so the divisors come out sorted without ever being collected or hashed.
+/// The heap stays far smaller than the number of divisors.
+pub struct Divisors<T> {
+    /// ``(base, exponent)`` pairs that the divisors are products of powers of
+    factors: Vec<(T, u32)>,
+    /// ``(divisor, index of its largest base, exponent of that base)``
+    heap: BinaryHeap<Reverse<(T, usize, u32)>>,
+    lo: T,
+    hi: T,
+}
+
+impl<T: PrimInt> Divisors<T> {
+    /// All of the divisors, including 1 and the integer itself
+    ///
+    /// Panics if the integer doesn't fit in ``T``.
+    pub fn new(factorization: &Factorization<T>) -> Divisors<T> {
+        Divisors::in_range(factorization, ..)
+    }
+
+    /// The divisors smaller than the integer itself
+    pub fn proper(factorization: &Factorization<T>) -> Divisors<T> {
+        let n = integer(factorization);
+        Divisors::in_range(factorization, ..n)
+    }
+
+    /// The divisors ``d`` of ``n`` with ``d`` and ``n / d`` co-prime
+    ///
+    /// These are the products of the full prime powers ``p^e`` dividing ``n``, so there are
+    /// ``2^ω(n)`` of them.
+    pub fn unitary(factorization: &Factorization<T>) -> Divisors<T> {
+        let n = integer(factorization);
+        let mut prime_powers: Vec<_> = factorization
+            .factors()
+            .iter()
+            .map(|&(p, e)| (p.pow(e), 1))
+            .collect();
+        prime_powers.sort_unstable();
+
+        Divisors::from_factors(prime_powers, T::one(), n)
+    }
+
+    /// The divisors that lie within ``range``
+    ///
+    /// No divisor above the range is ever generated, so a small range near the bottom is cheap.
+    pub fn in_range<R: RangeBounds<T>>(factorization: &Factorization<T>, range: R) -> Divisors<T> {
+        let n = integer(factorization);
+
+        let lo = match range.start_bound() {
+            Bound::Included(&lo) => lo,
+            Bound::Excluded(&lo) => lo.saturating_add(T::one()),
+            Bound::Unbounded => T::one(),
+        };
+        let hi = match range.end_bound() {
+            Bound::Included(&hi) => hi.min(n),
+            Bound::Excluded(&hi) if hi <= T::one() => T::zero(),
+            Bound::Excluded(&hi) => (hi - T::one()).min(n),
+            Bound::Unbounded => n,
+        };
+
+        Divisors::from_factors(factorization.factors().to_vec(), lo, hi)
+    }
+
+    fn from_factors(factors: Vec<(T, u32)>, lo: T, hi: T) -> Divisors<T> {
+        let mut heap = BinaryHeap::new();
+        if hi >= T::one() {
+            // 1 is the root, and is marked by an exponent of 0
+            heap.push(Reverse((T::one(), 0, 0)));
+        }
+
+        Divisors {
+            factors,
+            heap,
+            lo,
+            hi,
+        }
+    }
+
+    fn push(&mut self, divisor: Option<T>, index: usize, exponent: u32) {
+        // Successors only grow, so anything past the upper bound can be dropped along with them
+        if let Some(divisor) = divisor.filter(|&d| d <= self.hi) {
+            self.heap.push(Reverse((divisor, index, exponent)));
+        }
+    }
+}
+
+/// The integer given by a factorisation, which must fit in ``T``
+fn integer<T: PrimInt>(factorization: &Factorization<T>) -> T {
+    factorization
+        .to_integer()
+        .expect("the divisors of an integer that overflows its type can't be listed")
+}
+
+impl<T: PrimInt> Iterator for Divisors<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        loop {
+            let Reverse((divisor, index, exponent)) = self.heap.pop()?;
+
+            if exponent == 0 {
+                // The root, whose only successor is the first base
+                if let Some(&(base, _)) = self.factors.first() {
+                    self.push(divisor.checked_mul(&base), 0, 1);
+                }
+            } else {
+                let (base, max_exponent) = self.factors[index];
+                if exponent < max_exponent {
+                    self.push(divisor.checked_mul(&base), index, exponent + 1);
+                }
+
+                if let Some(&(next_base, _)) = self.factors.get(index + 1) {
+                    self.push(divisor.checked_mul(&next_base), index + 1, 1);
+                    if exponent == 1 {
+                        let sibling = (divisor / base).checked_mul(&next_base);
+                        self.push(sibling, index + 1, 1);
+                    }
+                }
+            }
+
+            if divisor >= self.lo {
+                return Some(divisor);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::factorize;
+
+    fn all_divisors(n: u64) -> Vec<u64> {
+        (1..=n).filter(|d| n.is_multiple_of(*d)).collect()
+    }
+
+    #[test]
+    fn test_matches_trial_division() {
+        for n in 1..=3_000u64 {
+            let factors = factorize(n);
+            let expected = all_divisors(n);
+
+            assert_eq!(Divisors::new(&factors).collect::<Vec<_>>(), expected, "{n}");
+            assert_eq!(
+                Divisors::proper(&factors).collect::<Vec<_>>(),
+                expected[..expected.len() - 1],
+                "{n}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_unitary() {
+        for n in 1..=3_000u64 {
+            let expected: Vec<_> = all_divisors(n)
+                .into_iter()
+                .filter(|&d| factorize(d).gcd(&factorize(n / d)) == Factorization::one())
+                .collect();
+
+            assert_eq!(Divisors::unitary(&factorize(n)).collect::<Vec<_>>(), expected, "{n}");
+        }
+
+        assert_eq!(
+            Divisors::unitary(&factorize(360u64)).collect::<Vec<_>>(),
+            [1, 5, 8, 9, 40, 45, 72, 360]
+        );
+    }
+
+    #[test]
+    fn test_in_range() {
+        let n = 720_720u64;
+        let factors = factorize(n);
+        let divisors = all_divisors(n);
+
+        for (lo, hi) in [(0, 0), (1, 1), (10, 100), (100, 10), (700_000, 800_000), (5, 5)] {
+            let expected: Vec<_> = divisors
+                .iter()
+                .copied()
+                .filter(|&d| lo <= d && d <= hi)
+                .collect();
+
+            let actual: Vec<_> = Divisors::in_range(&factors, lo..=hi).collect();
+            assert_eq!(actual, expected, "[{lo}, {hi}]");
+        }
+
+        assert_eq!(Divisors::in_range(&factors, ..1).count(), 0);
+        assert_eq!(Divisors::in_range(&factors, ..).count(), divisors.len());
+    }
+
+    #[test]
+    fn test_large() {
+        // 2^64 - 1 has seven distinct prime factors, and its largest divisors need the full width
+        let divisors: Vec<_> = Divisors::new(&factorize(u64::MAX)).collect();
+
+        assert_eq!(divisors.len(), 128);
+        assert!(divisors.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(divisors[..4], [1, 3, 5, 15]);
+        assert_eq!(divisors.last(), Some(&u64::MAX));
+    }
+}
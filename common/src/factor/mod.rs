@@ -0,0 +1,300 @@
+pub mod pollard_rho;
+
+use crate::divisors::Divisors;
+use crate::factor::pollard_rho::{factorize, Factorize};
+use num::traits::PrimInt;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Mul;
+
+/// The prime factorisation of an integer, held as ``(prime, exponent)`` pairs sorted by prime
+///
+/// 1 has the empty factorisation.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Factorization<T> {
+    factors: Vec<(T, u32)>,
+}
+
+impl<T: PrimInt> Factorization<T> {
+    /// Build the factorisation from a list of prime factors, in any order and with repeats
+    pub(crate) fn from_primes(mut primes: Vec<T>) -> Factorization<T> {
+        primes.sort_unstable();
+
+        let mut factors: Vec<(T, u32)> = Vec::new();
+        for p in primes {
+            match factors.last_mut() {
+                Some((last, exponent)) if *last == p => *exponent += 1,
+                _ => factors.push((p, 1)),
+            }
+        }
+
+        Factorization { factors }
+    }
+
+    /// The ``(prime, exponent)`` pairs, sorted by prime
+    pub fn factors(&self) -> &[(T, u32)] {
+        &self.factors
+    }
+
+    /// The distinct prime factors in ascending order
+    pub fn primes(&self) -> impl Iterator<Item = T> + '_ {
+        self.factors.iter().map(|&(p, _)| p)
+    }
+
+    /// The number of distinct prime factors, ω(n)
+    pub fn num_distinct(&self) -> usize {
+        self.factors.len()
+    }
+
+    /// The number of prime factors counted with multiplicity, Ω(n)
+    pub fn num_total(&self) -> u32 {
+        self.factors.iter().map(|&(_, e)| e).sum()
+    }
+
+    pub fn is_prime(&self) -> bool {
+        matches!(self.factors[..], [(_, 1)])
+    }
+
+    /// The factorisation of 1
+    pub fn one() -> Factorization<T> {
+        Factorization {
+            factors: Vec::new(),
+        }
+    }
+
+    /// The factorisation of ``n^k``, found by scaling every exponent by ``k``
+    ///
+    /// Any power of 1, and ``n^0``, is 1.
+    pub fn pow(&self, k: u32) -> Factorization<T> {
+        if k == 0 {
+            return Factorization::one();
+        }
+
+        let factors = self.factors.iter().map(|&(p, e)| (p, e * k)).collect();
+        Factorization { factors }
+    }
+
+    /// The greatest common divisor, taking the smaller exponent of each prime
+    pub fn gcd(&self, other: &Factorization<T>) -> Factorization<T> {
+        self.merge(other, u32::min)
+    }
+
+    /// The least common multiple, taking the larger exponent of each prime
+    pub fn lcm(&self, other: &Factorization<T>) -> Factorization<T> {
+        self.merge(other, u32::max)
+    }
+
+    /// Combine the exponents of each prime in either factorisation, treating a missing prime as
+    /// an exponent of zero
+    fn merge<F>(&self, other: &Factorization<T>, combine: F) -> Factorization<T>
+    where
+        F: Fn(u32, u32) -> u32,
+    {
+        let mut left = self.factors.iter().peekable();
+        let mut right = other.factors.iter().peekable();
+        let mut factors = Vec::new();
+
+        loop {
+            let (p, e) = match (left.peek(), right.peek()) {
+                (Some(&&(p, e_left)), Some(&&(q, e_right))) => match p.cmp(&q) {
+                    Ordering::Less => {
+                        left.next();
+                        (p, combine(e_left, 0))
+                    }
+                    Ordering::Greater => {
+                        right.next();
+                        (q, combine(0, e_right))
+                    }
+                    Ordering::Equal => {
+                        left.next();
+                        right.next();
+                        (p, combine(e_left, e_right))
+                    }
+                },
+                (Some(&&(p, e)), None) => {
+                    left.next();
+                    (p, combine(e, 0))
+                }
+                (None, Some(&&(q, e))) => {
+                    right.next();
+                    (q, combine(0, e))
+                }
+                (None, None) => break,
+            };
+
+            if e > 0 {
+                factors.push((p, e));
+            }
+        }
+
+        Factorization { factors }
+    }
+
+    /// Test if the integer is a perfect square, i.e. every exponent is even
+    pub fn is_square(&self) -> bool {
+        self.factors.iter().all(|&(_, e)| e.is_multiple_of(2))
+    }
+
+    /// Test if no prime divides the integer more than once
+    pub fn is_squarefree(&self) -> bool {
+        self.factors.iter().all(|&(_, e)| e == 1)
+    }
+
+    /// The number of divisors τ(n)
+    pub fn divisor_count(&self) -> u64 {
+        self.factors.iter().map(|&(_, e)| e as u64 + 1).product()
+    }
+
+    /// The sum of the divisors σ(n), or ``None`` if it overflows ``T``
+    pub fn divisor_sum(&self) -> Option<T> {
+        self.factors.iter().try_fold(T::one(), |sum, &(p, e)| {
+            // 1 + p + ... + p^e
+            let mut power = T::one();
+            let mut term = T::one();
+            for _ in 0..e {
+                power = power.checked_mul(&p)?;
+                term = term.checked_add(&power)?;
+            }
+            sum.checked_mul(&term)
+        })
+    }
+
+    /// The divisors in ascending order, including 1 and the integer itself
+    ///
+    /// Panics if the integer doesn't fit in ``T``. See ``Divisors`` for the other variants.
+    pub fn divisors(&self) -> Divisors<T> {
+        Divisors::new(self)
+    }
+
+    /// Multiply the factors back out, or ``None`` if the integer overflows ``T``
+    pub fn to_integer(&self) -> Option<T> {
+        self.factors.iter().try_fold(T::one(), |n, &(p, e)| {
+            (0..e).try_fold(n, |n, _| n.checked_mul(&p))
+        })
+    }
+}
+
+/// Test if ``n`` has no repeated prime factor, with 0 treated as not square-free
+pub fn is_squarefree<T: Factorize + PrimInt>(n: T) -> bool {
+    n != T::zero() && factorize(n).is_squarefree()
+}
+
+impl<T: PrimInt> Mul for &Factorization<T> {
+    type Output = Factorization<T>;
+
+    /// The factorisation of the product, adding the exponents of each prime
+    fn mul(self, other: &Factorization<T>) -> Factorization<T> {
+        self.merge(other, |a, b| a + b)
+    }
+}
+
+impl<T: PrimInt> Mul for Factorization<T> {
+    type Output = Factorization<T>;
+
+    fn mul(self, other: Factorization<T>) -> Factorization<T> {
+        &self * &other
+    }
+}
+
+/// Formats as the integer followed by its prime powers, e.g. "90 = 2^1 × 3^2 × 5^1"
+///
+/// The integer is left out if it would overflow ``T``.
+impl<T: PrimInt + fmt::Display> fmt::Display for Factorization<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(n) = self.to_integer() {
+            write!(f, "{n} = ")?;
+        }
+
+        if self.factors.is_empty() {
+            return write!(f, "1");
+        }
+
+        for (i, (p, e)) in self.factors.iter().enumerate() {
+            if i > 0 {
+                write!(f, " × ")?;
+            }
+            write!(f, "{p}^{e}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::arith::linear_sieve::multiplicative_table;
+    use crate::arith::multiplicative::Mobius;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(factorize(90u64).to_string(), "90 = 2^1 × 3^2 × 5^1");
+        assert_eq!(factorize(1u64).to_string(), "1 = 1");
+        assert_eq!(factorize(97u32).to_string(), "97 = 97^1");
+
+        // Too large for u32, so only the factors are shown
+        assert_eq!(factorize(65_536u32).pow(2).to_string(), "2^32");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = factorize(360u64); // 2^3 * 3^2 * 5
+        let b = factorize(700u64); // 2^2 * 5^2 * 7
+
+        assert_eq!(&a * &b, factorize(252_000u64));
+        assert_eq!(a.gcd(&b), factorize(20u64));
+        assert_eq!(a.lcm(&b), factorize(12_600u64));
+        assert_eq!(a.pow(3), factorize(46_656_000u64));
+        assert_eq!(a.pow(0), Factorization::one());
+        assert_eq!(a.clone() * Factorization::one(), a);
+        assert_eq!(a.gcd(&factorize(77u64)), Factorization::one());
+        assert_eq!(Factorization::one().lcm(&b), b);
+    }
+
+    #[test]
+    fn test_is_square() {
+        for n in 1..=1_000u64 {
+            let root = n.isqrt();
+            assert_eq!(factorize(n).is_square(), root * root == n, "{n}");
+        }
+    }
+
+    #[test]
+    fn test_is_squarefree() {
+        let mobius = multiplicative_table(10_000, &Mobius);
+
+        for n in 0..=10_000u64 {
+            assert_eq!(is_squarefree(n), mobius[n as usize] != 0, "{n}");
+        }
+        assert!(is_squarefree(u64::MAX));
+        assert!(!is_squarefree(1u128 << 100));
+    }
+
+    #[test]
+    fn test_divisors() {
+        for n in 1..=2_000u64 {
+            let factors = factorize(n);
+            let expected: Vec<_> = (1..=n).filter(|d| n.is_multiple_of(*d)).collect();
+
+            assert_eq!(factors.divisors().collect::<Vec<_>>(), expected, "{n}");
+            assert_eq!(factors.divisor_count(), expected.len() as u64, "{n}");
+            assert_eq!(factors.divisor_sum(), Some(expected.iter().sum()), "{n}");
+        }
+    }
+
+    #[test]
+    fn test_overflow() {
+        let factors = factorize(u32::MAX);
+
+        assert_eq!(factors.to_integer(), Some(u32::MAX));
+        assert_eq!(factors.divisor_sum(), None);
+        assert_eq!(factors.divisor_count(), 32);
+        assert_eq!((&factors * &factors).to_integer(), None);
+        assert_eq!(factorize(1u32 << 31).pow(2).to_integer(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_divisors_overflow() {
+        factorize(u16::MAX as u32).pow(3).divisors().count();
+    }
+}
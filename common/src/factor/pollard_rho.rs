@@ -0,0 +1,292 @@
+use crate::factor::Factorization;
+use crate::primes::miller_rabin::Primality;
+use crate::primes::modular::{ModArith, Montgomery128};
+use num::traits::PrimInt;
+
+/// Factors below this are removed by trial division before resorting to Pollard-rho
+const TRIAL_DIVISION_LIMIT: u32 = 1_000;
+
+/// Number of steps between each gcd in Brent's variant of Pollard-rho
+const GCD_BATCH: usize = 128;
+
+/// Integer types that can be broken into their prime factors
+pub trait Factorize: Sized {
+    fn factorize(self) -> Factorization<Self>;
+}
+
+/// Break ``n`` down into its prime factors
+///
+/// Small factors are found by trial division, and the remaining cofactor is split with
+/// Pollard-rho (using Brent's cycle detection) until Miller-Rabin reports each part as prime. A
+/// 20-digit number takes milliseconds, rather than the hours needed by trial division.
+///
+/// Panics if ``n`` is zero.
+pub fn factorize<T: Factorize>(n: T) -> Factorization<T> {
+    n.factorize()
+}
+
+impl Factorize for u32 {
+    fn factorize(self) -> Factorization<u32> {
+        let (mut primes, cofactor) = trial_division(self);
+        split(cofactor, &mut primes);
+        Factorization::from_primes(primes)
+    }
+}
+
+impl Factorize for u64 {
+    fn factorize(self) -> Factorization<u64> {
+        let (mut primes, cofactor) = trial_division(self);
+        split(cofactor, &mut primes);
+        Factorization::from_primes(primes)
+    }
+}
+
+impl Factorize for u128 {
+    fn factorize(self) -> Factorization<u128> {
+        let (mut primes, cofactor) = trial_division(self);
+        split_u128(cofactor, &mut primes);
+        Factorization::from_primes(primes)
+    }
+}
+
+/// Remove the factors below ``TRIAL_DIVISION_LIMIT``, returning them and the remaining cofactor
+fn trial_division<T: PrimInt>(n: T) -> (Vec<T>, T) {
+    assert!(n != T::zero(), "0 has no prime factorisation");
+
+    let mut primes = Vec::new();
+    let mut n = n;
+
+    let candidates = std::iter::once(2).chain((3..TRIAL_DIVISION_LIMIT).step_by(2));
+    for d in candidates.map(|d| T::from(d).unwrap()) {
+        if d * d > n {
+            break;
+        }
+        while n % d == T::zero() {
+            primes.push(d);
+            n = n / d;
+        }
+    }
+
+    // Anything left below the square of the limit has no smaller factors, so must be prime
+    let limit = T::from(TRIAL_DIVISION_LIMIT).unwrap();
+    if n > T::one() && n < limit * limit {
+        primes.push(n);
+        n = T::one();
+    }
+
+    (primes, n)
+}
+
+/// Split ``n`` into its prime factors, adding them to ``primes``
+fn split<T: PrimInt + ModArith + Primality>(n: T, primes: &mut Vec<T>) {
+    if n == T::one() {
+        return;
+    }
+    if n.is_prime() {
+        primes.push(n);
+        return;
+    }
+
+    let divisor = pollard_brent(n, |a, b| a.mul_mod(b, n));
+    split(divisor, primes);
+    split(n / divisor, primes);
+}
+
+/// Split a ``u128``, narrowing to the much faster ``u64`` arithmetic once the parts fit
+fn split_u128(n: u128, primes: &mut Vec<u128>) {
+    if n <= u64::MAX as u128 {
+        let mut narrow_primes = Vec::new();
+        split(n as u64, &mut narrow_primes);
+        primes.extend(narrow_primes.into_iter().map(u128::from));
+        return;
+    }
+    if n.is_prime() {
+        primes.push(n);
+        return;
+    }
+
+    let montgomery = Montgomery128::new(n);
+    let divisor = pollard_brent(n, |a, b| montgomery.mul(a, b));
+    split_u128(divisor, primes);
+    split_u128(n / divisor, primes);
+}
+
+/// Find a non-trivial divisor of the odd composite ``n``
+///
+/// Pollard-rho with Brent's cycle detection, multiplying batches of differences together so only
+/// one gcd is needed per ``GCD_BATCH`` steps. If a batch overshoots the factor, we step back
+/// through it one gcd at a time; if the cycle closes without a factor we retry with a new
+/// polynomial.
+///
+/// ``mul`` may work in a Montgomery representation: that only rescales each difference by a unit,
+/// which leaves the gcds unchanged.
+fn pollard_brent<T, F>(n: T, mul: F) -> T
+where
+    T: PrimInt + ModArith,
+    F: Fn(T, T) -> T,
+{
+    let mut c = T::one();
+
+    loop {
+        let f = |x: T| mul(x, x).add_mod(c, n);
+        let abs_diff = |a: T, b: T| if a > b { a - b } else { b - a };
+
+        let mut y = T::from(2).unwrap();
+        let mut x = y;
+        let mut saved_y = y;
+        let mut product = T::one();
+        let mut divisor = T::one();
+        let mut cycle_length = 1;
+
+        while divisor == T::one() {
+            x = y;
+            for _ in 0..cycle_length {
+                y = f(y);
+            }
+
+            let mut steps = 0;
+            while steps < cycle_length && divisor == T::one() {
+                saved_y = y;
+                for _ in 0..GCD_BATCH.min(cycle_length - steps) {
+                    y = f(y);
+                    product = mul(product, abs_diff(x, y));
+                }
+                divisor = gcd(product, n);
+                steps += GCD_BATCH;
+            }
+            cycle_length *= 2;
+        }
+
+        if divisor == n {
+            // Overshot within the last batch, so retrace it step by step
+            loop {
+                saved_y = f(saved_y);
+                divisor = gcd(abs_diff(x, saved_y), n);
+                if divisor != T::one() {
+                    break;
+                }
+            }
+        }
+
+        if divisor != n {
+            return divisor;
+        }
+        c = c + T::one();
+    }
+}
+
+/// Binary gcd
+pub(crate) fn gcd<T: PrimInt>(a: T, b: T) -> T {
+    if a == T::zero() {
+        return b;
+    } else if b == T::zero() {
+        return a;
+    }
+
+    let shift = (a | b).trailing_zeros() as usize;
+    let mut a = a >> a.trailing_zeros() as usize;
+    let mut b = b;
+
+    while b != T::zero() {
+        b = b >> b.trailing_zeros() as usize;
+        if a > b {
+            std::mem::swap(&mut a, &mut b);
+        }
+        b = b - a;
+    }
+
+    a << shift
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Reference factorisation by trial division
+    fn trial_factors(n: u64) -> Vec<(u64, u32)> {
+        let mut factors = Vec::new();
+        let mut n = n;
+        let mut d = 2;
+
+        while d * d <= n {
+            let mut exponent = 0;
+            while n.is_multiple_of(d) {
+                n /= d;
+                exponent += 1;
+            }
+            if exponent > 0 {
+                factors.push((d, exponent));
+            }
+            d += 1;
+        }
+        if n > 1 {
+            factors.push((n, 1));
+        }
+
+        factors
+    }
+
+    #[test]
+    fn test_small_values() {
+        assert!(factorize(1_u64).factors().is_empty());
+
+        for n in 1..20_000_u64 {
+            let expected = trial_factors(n);
+
+            assert_eq!(factorize(n).factors(), expected, "{n}");
+            assert_eq!(factorize(n as u32).factors().len(), expected.len(), "{n}");
+        }
+    }
+
+    #[test]
+    fn test_semiprimes() {
+        let (p, q) = (1_000_000_007_u64, 9_999_999_967_u64);
+        assert_eq!(factorize(p * q).factors(), [(p, 1), (q, 1)]);
+
+        let p = 4_294_967_291_u64;
+        assert_eq!(factorize(p * p).factors(), [(p, 2)]);
+
+        let (p, q) = (65_521_u32, 65_519_u32);
+        assert_eq!(factorize(p * q).factors(), [(q, 1), (p, 1)]);
+    }
+
+    #[test]
+    fn test_u64_extremes() {
+        let factors = factorize(u64::MAX);
+        let expected = [(3, 1), (5, 1), (17, 1), (257, 1), (641, 1), (65_537, 1), (6_700_417, 1)];
+
+        assert_eq!(factors.factors(), expected);
+        assert!(factorize(u64::MAX - 58).is_prime());
+    }
+
+    #[test]
+    fn test_twenty_digit_values() {
+        // 10^20 - 1 = (10^10 - 1) * (10^10 + 1)
+        let factors = factorize(100_000_000_000_000_000_000_u128 - 1);
+        let expected = [
+            (3, 2),
+            (11, 1),
+            (41, 1),
+            (101, 1),
+            (271, 1),
+            (3_541, 1),
+            (9_091, 1),
+            (27_961, 1),
+        ];
+        assert_eq!(factors.factors(), expected);
+
+        let large_prime = 18_446_744_073_709_551_629_u128;
+        let factors = factorize(1_000_003 * large_prime);
+        assert_eq!(factors.factors(), [(1_000_003, 1), (large_prime, 1)]);
+        assert_eq!(factors.num_total(), 2);
+    }
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(gcd(0_u64, 5), 5);
+        assert_eq!(gcd(12_u64, 0), 12);
+        assert_eq!(gcd(12_u64, 18), 6);
+        assert_eq!(gcd(17_u128, 19), 1);
+        assert_eq!(gcd(1_u32 << 20, 3 << 10), 1 << 10);
+    }
+}
@@ -1,4 +1,45 @@
-use num::traits::{PrimInt, Unsigned};
+use num::traits::PrimInt;
+
+pub mod aliquot;
+pub mod arith;
+pub mod digits;
+pub mod dirichlet;
+pub mod divisors;
+pub mod factor;
+pub mod primes;
+
+pub use aliquot::{aliquot_sum, AliquotOutcome, AliquotSequence, AliquotTable, Chain};
+pub use arith::divisor_sum::{divisor_sums, divisor_sums_parallel};
+pub use arith::linear_sieve::{multiplicative_table, ArithmeticTables};
+pub use arith::multiplicative::{DivisorSigma, Mobius, MultiplicativeFunction, NumDivisors, Totient};
+pub use arith::summatory::{
+    count_reduced_fractions, count_squarefree, farey_length, mertens, totient_sum,
+};
+pub use digits::dp::{DigitAutomaton, DigitDp};
+pub use digits::palindrome::{is_palindrome, Palindromes};
+pub use digits::pandigital::{
+    concatenated_product, is_pandigital, is_pandigital_concat, Pandigitals,
+};
+pub use digits::signature::{group_by_padded_signature, group_by_signature, DigitSignature};
+pub use digits::{
+    digit_product, digit_sum, digits_be, digits_le, from_digits, num_digits, reverse_digits, Digits,
+};
+pub use dirichlet::{
+    divisor_count_summatory, divisor_sum_summatory, hyperbola_sum, ArithmeticFunction, FloorBlock,
+    FloorBlocks, Table,
+};
+pub use divisors::Divisors;
+pub use factor::pollard_rho::{factorize, Factorize};
+pub use factor::{is_squarefree, Factorization};
+pub use primes::cache::CacheError;
+pub use primes::counting::{prime_pi, prime_sum};
+pub use primes::miller_rabin::{is_prime, Primality};
+pub use primes::parallel::{get_primes_in_range_parallel, get_primes_up_to_parallel};
+pub use primes::segmented::{get_primes_in_range, primes_in_range, SegmentedPrimes};
+pub use primes::spf::SpfSieve;
+pub use primes::unbounded::{PrimeCache, Primes};
+pub use primes::wheel::PrimeSieve;
+
 pub fn get_primes_up_to(n: usize) -> Vec<u64> {
     let mut primes = Vec::new();
     let mut is_prime = vec![true; n + 1];
@@ -18,46 +59,27 @@ pub fn get_primes_up_to(n: usize) -> Vec<u64> {
     primes
 }
 
+/// The decimal digits of ``num`` in ascending order, with none at all for 0
 pub fn get_digits_sorted<T>(num: T) -> Vec<T>
     where T: std::ops::DivAssign + PrimInt + Copy + From<u8>
 {
-    let mut digits = Vec::new();
-    let mut num = num;
-    let ten = 10.into();
-
-    while num > T::zero() {
-        digits.push(num % ten);
-        num /= ten;
-    }
+    let mut digits = get_digits_gen(num);
     digits.sort_unstable();
     digits
 }
 
+/// The decimal digits of ``num``, most significant first, with none at all for 0
 pub fn get_digits(num: u32) -> Vec<u32> {
-    let mut digits = Vec::new();
-    let mut num = num;
-
-    while num > 0 {
-        digits.push(num % 10);
-        num /= 10;
-    }
-
-    digits.reverse();
-    digits
+    get_digits_gen(num)
 }
 
+/// The decimal digits of ``num``, most significant first, with none at all for 0
 pub fn get_digits_gen<T>(num: T) -> Vec<T> where
     T: std::ops::DivAssign + PrimInt + Copy + From<u8>
 {
-    let mut digits = Vec::new();
-    let mut num = num;
-    let ten = 10.into();
-
-    while num > T::zero() {
-        digits.push(num % ten);
-        num /= ten;
+    if num == T::zero() {
+        return Vec::new();
     }
 
-    digits.reverse();
-    digits
+    digits_be(num, 10).map(|digit| (digit as u8).into()).collect()
 }
@@ -0,0 +1,315 @@
+use crate::primes::wheel::PrimeSieve;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Identifies a file as a cached ``PrimeSieve``
+const MAGIC: [u8; 8] = *b"PESIEVE\0";
+
+/// Bumped whenever the layout of the file or the sieve bitset changes
+const FORMAT_VERSION: u32 = 1;
+
+/// Magic, version, bound, payload length and checksum, all little-endian
+const HEADER_LEN: usize = 8 + 4 + 8 + 8 + 8;
+
+/// Reasons a cache file couldn't be used
+#[derive(Debug)]
+pub enum CacheError {
+    Io(io::Error),
+    /// The file doesn't start with the expected magic bytes
+    BadMagic,
+    /// The file was written by a different version of the format
+    UnsupportedVersion(u32),
+    /// The file is shorter than its header says, or the payload doesn't fit the bound
+    Truncated,
+    /// The payload doesn't match its checksum
+    ChecksumMismatch,
+    /// The cached sieve stops short of the requested limit
+    BoundTooSmall {
+        cached: u64,
+        requested: u64,
+    },
+}
+
+impl fmt::Display for CacheError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CacheError::Io(err) => write!(f, "couldn't read the prime cache: {err}"),
+            CacheError::BadMagic => write!(f, "not a prime cache file"),
+            CacheError::UnsupportedVersion(version) => write!(
+                f,
+                "prime cache format version {version} isn't supported (expected {FORMAT_VERSION})"
+            ),
+            CacheError::Truncated => write!(f, "prime cache file is truncated"),
+            CacheError::ChecksumMismatch => write!(f, "prime cache checksum doesn't match"),
+            CacheError::BoundTooSmall { cached, requested } => write!(
+                f,
+                "prime cache only covers up to {cached}, but {requested} was requested"
+            ),
+        }
+    }
+}
+
+impl Error for CacheError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CacheError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CacheError {
+    fn from(err: io::Error) -> Self {
+        CacheError::Io(err)
+    }
+}
+
+/// 64-bit FNV-1a hash, covering the bound as well as the bitset
+fn checksum(bound: u64, payload: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bound
+        .to_le_bytes()
+        .iter()
+        .chain(payload)
+        .fold(OFFSET_BASIS, |hash, &byte| {
+            (hash ^ byte as u64).wrapping_mul(PRIME)
+        })
+}
+
+/// Split ``n`` bytes off the front of ``bytes``
+fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Result<&'a [u8], CacheError> {
+    if bytes.len() < n {
+        return Err(CacheError::Truncated);
+    }
+    let (head, tail) = bytes.split_at(n);
+    *bytes = tail;
+    Ok(head)
+}
+
+fn take_u32(bytes: &mut &[u8]) -> Result<u32, CacheError> {
+    Ok(u32::from_le_bytes(take(bytes, 4)?.try_into().unwrap()))
+}
+
+fn take_u64(bytes: &mut &[u8]) -> Result<u64, CacheError> {
+    Ok(u64::from_le_bytes(take(bytes, 8)?.try_into().unwrap()))
+}
+
+impl PrimeSieve {
+    /// Write the sieve to ``path`` so that it can be read back with ``load``
+    ///
+    /// The file is written alongside and then renamed into place, so a reader never sees a
+    /// partially written cache.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let payload = self.as_bytes();
+
+        let mut file = Vec::with_capacity(HEADER_LEN + payload.len());
+        file.extend_from_slice(&MAGIC);
+        file.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        file.extend_from_slice(&self.limit().to_le_bytes());
+        file.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+        file.extend_from_slice(&checksum(self.limit(), payload).to_le_bytes());
+        file.extend_from_slice(payload);
+
+        let partial = path.with_extension("partial");
+        fs::write(&partial, file)?;
+        fs::rename(&partial, path)
+    }
+
+    /// Read a sieve covering at least ``limit`` from a file written by ``save``
+    ///
+    /// The whole file is read at once and validated against its header and checksum before use.
+    /// If the cached sieve goes beyond ``limit`` it is cut down, so the result is identical to
+    /// ``PrimeSieve::new(limit)``.
+    pub fn load<P: AsRef<Path>>(path: P, limit: u64) -> Result<PrimeSieve, CacheError> {
+        let file = fs::read(path)?;
+        let mut bytes = file.as_slice();
+
+        if take(&mut bytes, MAGIC.len())? != MAGIC {
+            return Err(CacheError::BadMagic);
+        }
+        let version = take_u32(&mut bytes)?;
+        if version != FORMAT_VERSION {
+            return Err(CacheError::UnsupportedVersion(version));
+        }
+
+        let bound = take_u64(&mut bytes)?;
+        let payload_len = take_u64(&mut bytes)?;
+        let expected_checksum = take_u64(&mut bytes)?;
+        if bytes.len() as u64 != payload_len {
+            return Err(CacheError::Truncated);
+        }
+        if checksum(bound, bytes) != expected_checksum {
+            return Err(CacheError::ChecksumMismatch);
+        }
+        if bound < limit {
+            return Err(CacheError::BoundTooSmall {
+                cached: bound,
+                requested: limit,
+            });
+        }
+
+        let mut sieve =
+            PrimeSieve::from_raw_parts(bound, bytes.to_vec()).ok_or(CacheError::Truncated)?;
+        sieve.truncate(limit);
+        Ok(sieve)
+    }
+
+    /// Load the sieve from the cache at ``path``, or sieve it afresh if the cache can't be used
+    ///
+    /// A missing, corrupt, outdated or too-small cache is replaced with the newly sieved primes.
+    /// Failing to write the cache isn't an error, as the sieve itself is still correct.
+    pub fn load_or_build<P: AsRef<Path>>(path: P, limit: u64) -> PrimeSieve {
+        let path = path.as_ref();
+
+        PrimeSieve::load(path, limit).unwrap_or_else(|_| {
+            let sieve = PrimeSieve::new(limit);
+            let _ = sieve.save(path);
+            sieve
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// A fresh path in the temp directory, unique to the test
+    fn cache_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("prime_cache_{}_{name}.bin", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn assert_same(actual: &PrimeSieve, expected: &PrimeSieve) {
+        assert_eq!(actual.limit(), expected.limit());
+        assert!(actual.primes().eq(expected.primes()));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let path = cache_path("round_trip");
+        let sieve = PrimeSieve::new(100_000);
+        sieve.save(&path).unwrap();
+
+        assert_same(&PrimeSieve::load(&path, 100_000).unwrap(), &sieve);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_load_smaller_limit() {
+        let path = cache_path("smaller_limit");
+        PrimeSieve::new(100_000).save(&path).unwrap();
+
+        for limit in [0, 1, 2, 7, 29, 30, 31, 1_000, 99_991, 99_999] {
+            let loaded = PrimeSieve::load(&path, limit).unwrap();
+            assert_same(&loaded, &PrimeSieve::new(limit));
+            assert_eq!(loaded.count(), PrimeSieve::new(limit).count(), "{limit}");
+        }
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_bound_too_small() {
+        let path = cache_path("too_small");
+        PrimeSieve::new(1_000).save(&path).unwrap();
+
+        assert!(matches!(
+            PrimeSieve::load(&path, 1_001),
+            Err(CacheError::BoundTooSmall {
+                cached: 1_000,
+                requested: 1_001
+            })
+        ));
+
+        // Rebuilding replaces the cache with one large enough
+        assert_same(
+            &PrimeSieve::load_or_build(&path, 5_000),
+            &PrimeSieve::new(5_000),
+        );
+        assert_eq!(PrimeSieve::load(&path, 5_000).unwrap().limit(), 5_000);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_missing_file() {
+        let path = cache_path("missing");
+
+        assert!(matches!(
+            PrimeSieve::load(&path, 10),
+            Err(CacheError::Io(_))
+        ));
+        assert_same(
+            &PrimeSieve::load_or_build(&path, 10_000),
+            &PrimeSieve::new(10_000),
+        );
+        assert!(path.exists());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_corrupt_files() {
+        let path = cache_path("corrupt");
+        PrimeSieve::new(10_000).save(&path).unwrap();
+        let good = fs::read(&path).unwrap();
+
+        let mut flipped = good.clone();
+        *flipped.last_mut().unwrap() ^= 0x10;
+        fs::write(&path, &flipped).unwrap();
+        assert!(matches!(
+            PrimeSieve::load(&path, 100),
+            Err(CacheError::ChecksumMismatch)
+        ));
+
+        // A changed bound is caught by the checksum too
+        let mut rebounded = good.clone();
+        rebounded[12] ^= 0x01;
+        fs::write(&path, &rebounded).unwrap();
+        assert!(matches!(
+            PrimeSieve::load(&path, 100),
+            Err(CacheError::ChecksumMismatch)
+        ));
+
+        fs::write(&path, &good[..good.len() - 1]).unwrap();
+        assert!(matches!(
+            PrimeSieve::load(&path, 100),
+            Err(CacheError::Truncated)
+        ));
+
+        fs::write(&path, &good[..10]).unwrap();
+        assert!(matches!(
+            PrimeSieve::load(&path, 100),
+            Err(CacheError::Truncated)
+        ));
+
+        let mut versioned = good.clone();
+        versioned[8] = 2;
+        fs::write(&path, &versioned).unwrap();
+        assert!(matches!(
+            PrimeSieve::load(&path, 100),
+            Err(CacheError::UnsupportedVersion(2))
+        ));
+
+        fs::write(&path, b"not a sieve at all, just some text").unwrap();
+        assert!(matches!(
+            PrimeSieve::load(&path, 100),
+            Err(CacheError::BadMagic)
+        ));
+
+        // Any corrupt cache is rebuilt rather than trusted
+        fs::write(&path, &flipped).unwrap();
+        assert_same(
+            &PrimeSieve::load_or_build(&path, 10_000),
+            &PrimeSieve::new(10_000),
+        );
+        assert_eq!(fs::read(&path).unwrap(), good);
+        fs::remove_file(path).unwrap();
+    }
+}
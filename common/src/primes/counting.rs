@@ -0,0 +1,108 @@
+use num::traits::PrimInt;
+
+/// The number of primes ``p <= x``, π(x)
+///
+/// Runs in roughly O(x^(3/4)) time and O(sqrt(x)) memory, taking a few seconds at 10^12, where a
+/// sieve would need the primes themselves.
+pub fn prime_pi(x: u64) -> u64 {
+    lucy_hedgehog(x, |v| v - 1, |_| 1)
+}
+
+/// The sum of the primes ``p <= x``
+///
+/// Same approach and cost as ``prime_pi``; the result needs ``u128`` as the sum passes
+/// ``u64::MAX`` shortly after 10^10.
+pub fn prime_sum(x: u64) -> u128 {
+    lucy_hedgehog(
+        x,
+        |v| {
+            let v = v as u128;
+            v * (v + 1) / 2 - 1
+        },
+        |p| p as u128,
+    )
+}
+
+/// Sum ``weight(p)`` over the primes ``p <= x``, for a completely multiplicative ``weight``
+///
+/// Lucy_Hedgehog's method: ``S(v)`` starts as the sum of ``weight(n)`` over ``2 <= n <= v``
+/// (given by ``initial``), and sieving out each prime ``p <= sqrt(x)`` removes the values whose
+/// smallest prime factor is ``p``, with ``S(v) -= weight(p) * (S(v / p) - S(p - 1))``.
+///
+/// Only the O(sqrt(x)) distinct values of ``x / i`` are ever needed, so ``S`` is stored for
+/// ``v <= sqrt(x)`` directly and for larger ``v = x / i`` by ``i``.
+fn lucy_hedgehog<T, F, W>(x: u64, initial: F, weight: W) -> T
+where
+    T: PrimInt,
+    F: Fn(u64) -> T,
+    W: Fn(u64) -> T,
+{
+    if x < 2 {
+        return T::zero();
+    }
+
+    let root = x.isqrt() as usize;
+
+    // small[v] = S(v) for v <= root, large[i] = S(x / i) for i <= root
+    let mut small: Vec<T> = (0..=root as u64)
+        .map(|v| if v < 2 { T::zero() } else { initial(v) })
+        .collect();
+    let mut large: Vec<T> = std::iter::once(T::zero())
+        .chain((1..=root as u64).map(|i| initial(x / i)))
+        .collect();
+
+    for p in 2..=root {
+        if small[p] == small[p - 1] {
+            // p was removed by a smaller prime, so it's composite
+            continue;
+        }
+
+        let below_p = small[p - 1];
+        let p_weight = weight(p as u64);
+        let p_squared = p * p;
+
+        let large_end = root.min(x as usize / p_squared);
+        for i in 1..=large_end {
+            let quotient = if i * p <= root {
+                large[i * p]
+            } else {
+                small[x as usize / (i * p)]
+            };
+            large[i] = large[i] - p_weight * (quotient - below_p);
+        }
+
+        for v in (p_squared..=root).rev() {
+            small[v] = small[v] - p_weight * (small[v / p] - below_p);
+        }
+    }
+
+    large[1]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::get_primes_up_to;
+
+    #[test]
+    fn test_matches_sieve() {
+        let primes = get_primes_up_to(20_000);
+
+        for x in (0..2_000).chain([9_999, 10_000, 19_321, 19_999]) {
+            let below: Vec<_> = primes.iter().copied().take_while(|&p| p <= x).collect();
+
+            assert_eq!(prime_pi(x), below.len() as u64, "{x}");
+            assert_eq!(prime_sum(x), below.iter().sum::<u64>() as u128, "{x}");
+        }
+    }
+
+    #[test]
+    fn test_known_values() {
+        assert_eq!(prime_pi(1_000_000), 78_498);
+        assert_eq!(prime_pi(1_000_000_000), 50_847_534);
+        assert_eq!(prime_pi(10_000_000_000), 455_052_511);
+
+        // Problem 10: the sum of the primes below two million
+        assert_eq!(prime_sum(2_000_000), 142_913_828_922);
+    }
+}
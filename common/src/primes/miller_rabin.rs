@@ -0,0 +1,290 @@
+use crate::primes::modular::ModArith;
+use num::traits::PrimInt;
+
+/// Primes used for trial division before the probable-prime tests
+const SMALL_PRIMES: [u32; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Witnesses that make Miller-Rabin deterministic below 4,759,123,141
+const WITNESSES_32: [u32; 3] = [2, 7, 61];
+
+/// Witnesses that make Miller-Rabin deterministic below 318,665,857,834,031,151,167,461, covering
+/// all of ``u64``
+const WITNESSES_64: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// Integer types with a deterministic primality test
+pub trait Primality: Copy {
+    fn is_prime(self) -> bool;
+}
+
+/// Test if ``n`` is prime
+///
+/// Uses deterministic Miller-Rabin witness sets for ``u32`` and ``u64``, and the Baillie-PSW test
+/// for ``u128`` values beyond ``u64::MAX``. There are no known BPSW pseudoprimes.
+pub fn is_prime<T: Primality>(n: T) -> bool {
+    n.is_prime()
+}
+
+impl Primality for u32 {
+    fn is_prime(self) -> bool {
+        match trial_division(self) {
+            Some(is_prime) => is_prime,
+            None => WITNESSES_32.iter().all(|&a| strong_probable_prime(self, a)),
+        }
+    }
+}
+
+impl Primality for u64 {
+    fn is_prime(self) -> bool {
+        match trial_division(self) {
+            Some(is_prime) => is_prime,
+            None => WITNESSES_64.iter().all(|&a| strong_probable_prime(self, a)),
+        }
+    }
+}
+
+impl Primality for u128 {
+    fn is_prime(self) -> bool {
+        if self <= u64::MAX as u128 {
+            return (self as u64).is_prime();
+        }
+
+        match trial_division(self) {
+            Some(is_prime) => is_prime,
+            None => strong_probable_prime(self, 2) && strong_lucas_probable_prime(self),
+        }
+    }
+}
+
+/// Settle primality by division by the small primes, if possible
+///
+/// After this returns ``None``, ``n`` is odd and larger than the square of the largest small prime.
+fn trial_division<T: PrimInt>(n: T) -> Option<bool> {
+    for p in SMALL_PRIMES {
+        let p = T::from(p).unwrap();
+        if n == p {
+            return Some(true);
+        } else if n % p == T::zero() {
+            return Some(false);
+        }
+    }
+
+    let largest = T::from(SMALL_PRIMES[SMALL_PRIMES.len() - 1]).unwrap();
+    if n < largest * largest {
+        Some(n > T::one())
+    } else {
+        None
+    }
+}
+
+/// Split an even ``n`` into ``d * 2^s`` with ``d`` odd
+fn split_powers_of_two<T: PrimInt>(n: T) -> (T, u32) {
+    let s = n.trailing_zeros();
+    (n >> s as usize, s)
+}
+
+/// Miller-Rabin round for an odd ``n`` with witness ``a``
+fn strong_probable_prime<T: ModArith>(n: T, a: T) -> bool {
+    let a = a % n;
+    if a == T::zero() {
+        return true;
+    }
+
+    let n_minus_one = n - T::one();
+    let (d, s) = split_powers_of_two(n_minus_one);
+
+    let mut x = a.pow_mod(d, n);
+    if x == T::one() || x == n_minus_one {
+        return true;
+    }
+
+    for _ in 1..s {
+        x = x.mul_mod(x, n);
+        if x == n_minus_one {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Jacobi symbol ``(a / n)`` for an odd ``n``
+fn jacobi(a: i128, n: u128) -> i32 {
+    let mut a = to_residue(a, n);
+    let mut n = n;
+    let mut result = 1;
+
+    while a != 0 {
+        while a.is_multiple_of(2) {
+            a /= 2;
+            if n % 8 == 3 || n % 8 == 5 {
+                result = -result;
+            }
+        }
+
+        std::mem::swap(&mut a, &mut n);
+        if a % 4 == 3 && n % 4 == 3 {
+            result = -result;
+        }
+        a %= n;
+    }
+
+    if n == 1 {
+        result
+    } else {
+        0
+    }
+}
+
+/// Reduce a signed value into ``[0, n)``
+fn to_residue(a: i128, n: u128) -> u128 {
+    let magnitude = a.unsigned_abs() % n;
+    if a < 0 && magnitude != 0 {
+        n - magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// Divide ``x`` by two modulo an odd ``n``
+fn half_mod(x: u128, n: u128) -> u128 {
+    if x.is_multiple_of(2) {
+        x / 2
+    } else {
+        // (x + n) / 2, without overflowing
+        x / 2 + n / 2 + 1
+    }
+}
+
+/// Strong Lucas probable prime test with Selfridge's parameters, the second half of BPSW
+///
+/// Expects an odd ``n`` with no small prime factors.
+fn strong_lucas_probable_prime(n: u128) -> bool {
+    // No suitable D exists for perfect squares
+    let root = n.isqrt();
+    if root * root == n {
+        return false;
+    }
+
+    // Find the first D in 5, -7, 9, -11, ... with (D / n) = -1
+    let mut d: i128 = 5;
+    loop {
+        match jacobi(d, n) {
+            -1 => break,
+            0 if d.unsigned_abs() != n => return false,
+            _ => d = if d > 0 { -(d + 2) } else { -d + 2 },
+        }
+    }
+
+    // P = 1, Q = (1 - D) / 4
+    let q = to_residue((1 - d) / 4, n);
+    let d = to_residue(d, n);
+
+    // n has no small factors, so n + 1 can't overflow
+    let (k, s) = split_powers_of_two(n + 1);
+
+    // Compute U_k, V_k and Q^k from the most significant bit down
+    let (mut u, mut v, mut q_k) = (1, 1, q);
+    for bit in (0..(127 - k.leading_zeros())).rev() {
+        u = u.mul_mod(v, n);
+        v = v.mul_mod(v, n).sub_mod(q_k.add_mod(q_k, n), n);
+        q_k = q_k.mul_mod(q_k, n);
+
+        if (k >> bit) & 1 == 1 {
+            let new_u = half_mod(u.add_mod(v, n), n);
+            let new_v = half_mod(d.mul_mod(u, n).add_mod(v, n), n);
+            (u, v) = (new_u, new_v);
+            q_k = q_k.mul_mod(q, n);
+        }
+    }
+
+    if u == 0 || v == 0 {
+        return true;
+    }
+
+    for _ in 1..s {
+        v = v.mul_mod(v, n).sub_mod(q_k.add_mod(q_k, n), n);
+        q_k = q_k.mul_mod(q_k, n);
+        if v == 0 {
+            return true;
+        }
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::get_primes_up_to;
+
+    #[test]
+    fn test_matches_sieve() {
+        let limit = 100_000;
+        let primes = get_primes_up_to(limit);
+
+        for n in 0..=limit as u64 {
+            let expected = primes.binary_search(&n).is_ok();
+
+            assert_eq!(is_prime(n), expected, "{n}");
+            assert_eq!(is_prime(n as u32), expected, "{n}");
+            assert_eq!(is_prime(n as u128), expected, "{n}");
+        }
+    }
+
+    #[test]
+    fn test_strong_pseudoprimes() {
+        // Strong pseudoprimes to several small bases, and Carmichael numbers
+        let composites: [u64; 8] = [
+            2_047,
+            3_215_031_751,
+            2_152_302_898_747,
+            3_474_749_660_383,
+            341_550_071_728_321,
+            3_825_123_056_546_413_051,
+            561,
+            1_105,
+        ];
+
+        for n in composites {
+            assert!(!is_prime(n), "{n}");
+        }
+        assert!(!is_prime(3_215_031_751_u32));
+    }
+
+    #[test]
+    fn test_large_u64() {
+        assert!(is_prime(u64::MAX - 58));
+        assert!(is_prime(4_294_967_291_u32));
+        assert!(is_prime(1_000_000_000_039_u64));
+        assert!(!is_prime(u64::MAX));
+        assert!(!is_prime(4_294_967_291_u64 * 4_294_967_279));
+    }
+
+    #[test]
+    fn test_large_u128() {
+        let mersenne_127 = (1_u128 << 127) - 1;
+        let mersenne_61 = (1_u128 << 61) - 1;
+        let mersenne_89 = (1_u128 << 89) - 1;
+
+        assert!(is_prime(mersenne_127));
+        assert!(is_prime(mersenne_89));
+        assert!(is_prime(u128::MAX - 158));
+        assert!(is_prime(18_446_744_073_709_551_629_u128));
+
+        assert!(!is_prime(u128::MAX));
+        assert!(!is_prime(mersenne_61 * mersenne_61));
+        assert!(!is_prime(mersenne_61 * 18_446_744_073_709_551_629));
+        assert!(!is_prime((1_u128 << 64) + 1));
+    }
+
+    #[test]
+    fn test_jacobi() {
+        // Values from the table at https://en.wikipedia.org/wiki/Jacobi_symbol
+        assert_eq!(jacobi(1, 1), 1);
+        assert_eq!(jacobi(2, 15), 1);
+        assert_eq!(jacobi(7, 15), -1);
+        assert_eq!(jacobi(5, 15), 0);
+        assert_eq!(jacobi(-1, 7), -1);
+        assert_eq!(jacobi(-7, 11), 1);
+    }
+}
@@ -0,0 +1,9 @@
+pub mod cache;
+pub mod counting;
+pub mod miller_rabin;
+pub(crate) mod modular;
+pub mod parallel;
+pub mod segmented;
+pub mod spf;
+pub mod unbounded;
+pub mod wheel;
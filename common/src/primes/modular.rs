@@ -0,0 +1,204 @@
+use num::traits::PrimInt;
+
+/// Modular arithmetic that never overflows, for any modulus representable in ``Self``.
+///
+/// Operands are expected to already be reduced below the modulus.
+pub(crate) trait ModArith: PrimInt {
+    fn mul_mod(self, rhs: Self, modulus: Self) -> Self;
+
+    fn add_mod(self, rhs: Self, modulus: Self) -> Self {
+        // Compare against the gap to the modulus rather than adding, which could overflow
+        if self >= modulus - rhs {
+            self - (modulus - rhs)
+        } else {
+            self + rhs
+        }
+    }
+
+    fn sub_mod(self, rhs: Self, modulus: Self) -> Self {
+        if self >= rhs {
+            self - rhs
+        } else {
+            modulus - (rhs - self)
+        }
+    }
+
+    /// Right-to-left binary exponentiation
+    fn pow_mod(self, exp: Self, modulus: Self) -> Self {
+        let mut result = Self::one() % modulus;
+        let mut base = self % modulus;
+        let mut exp = exp;
+
+        while exp > Self::zero() {
+            if exp & Self::one() == Self::one() {
+                result = result.mul_mod(base, modulus);
+            }
+            base = base.mul_mod(base, modulus);
+            exp = exp >> 1;
+        }
+
+        result
+    }
+}
+
+impl ModArith for u32 {
+    fn mul_mod(self, rhs: u32, modulus: u32) -> u32 {
+        (self as u64 * rhs as u64 % modulus as u64) as u32
+    }
+}
+
+impl ModArith for u64 {
+    fn mul_mod(self, rhs: u64, modulus: u64) -> u64 {
+        (self as u128 * rhs as u128 % modulus as u128) as u64
+    }
+}
+
+impl ModArith for u128 {
+    fn mul_mod(self, rhs: u128, modulus: u128) -> u128 {
+        if modulus <= u64::MAX as u128 {
+            return (self as u64).mul_mod(rhs as u64, modulus as u64) as u128;
+        }
+
+        // There's no wider type to promote to, so fall back to double-and-add
+        let mut result = 0;
+        let mut base = self;
+        let mut rhs = rhs;
+
+        while rhs > 0 {
+            if rhs & 1 == 1 {
+                result = result.add_mod(base, modulus);
+            }
+            base = base.add_mod(base, modulus);
+            rhs >>= 1;
+        }
+
+        result
+    }
+}
+
+/// Montgomery multiplication modulo an odd ``u128``
+///
+/// Values are held in Montgomery form, ``x * 2^128 mod n``, which replaces the slow double-and-add
+/// reduction with a few wide multiplications. Useful for long chains of products, where the
+/// conversion cost is paid once.
+pub(crate) struct Montgomery128 {
+    modulus: u128,
+    /// ``-modulus^-1 mod 2^128``
+    neg_inverse: u128,
+}
+
+impl Montgomery128 {
+    pub(crate) fn new(modulus: u128) -> Montgomery128 {
+        assert!(modulus % 2 == 1, "Montgomery form needs an odd modulus");
+
+        // Newton's iteration doubles the number of correct low bits each step, starting from 3
+        let mut inverse = modulus;
+        for _ in 0..7 {
+            inverse = inverse.wrapping_mul(2_u128.wrapping_sub(modulus.wrapping_mul(inverse)));
+        }
+
+        Montgomery128 {
+            modulus,
+            neg_inverse: inverse.wrapping_neg(),
+        }
+    }
+
+    /// The product ``a * b * 2^-128 mod n``
+    pub(crate) fn mul(&self, a: u128, b: u128) -> u128 {
+        let (hi, lo) = mul_wide(a, b);
+
+        // Add the multiple of n that clears the low 128 bits, then shift them away
+        let m = lo.wrapping_mul(self.neg_inverse);
+        let (m_hi, m_lo) = mul_wide(m, self.modulus);
+        let carry = lo.overflowing_add(m_lo).1 as u128;
+
+        let (sum, overflow) = hi.overflowing_add(m_hi);
+        let (sum, overflow_carry) = sum.overflowing_add(carry);
+        if overflow || overflow_carry || sum >= self.modulus {
+            sum.wrapping_sub(self.modulus)
+        } else {
+            sum
+        }
+    }
+}
+
+/// The full 256-bit product as ``(high, low)`` halves
+fn mul_wide(a: u128, b: u128) -> (u128, u128) {
+    let mask = u64::MAX as u128;
+    let (a_hi, a_lo) = (a >> 64, a & mask);
+    let (b_hi, b_lo) = (b >> 64, b & mask);
+
+    let lo_lo = a_lo * b_lo;
+    let hi_lo = a_hi * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_hi = a_hi * b_hi;
+
+    let middle = (lo_lo >> 64) + (hi_lo & mask) + (lo_hi & mask);
+    let lo = (lo_lo & mask) | (middle << 64);
+    let hi = hi_hi + (hi_lo >> 64) + (lo_hi >> 64) + (middle >> 64);
+
+    (hi, lo)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mul_mod_matches_wide_multiplication() {
+        let values = [0, 1, 2, 12_345, u32::MAX as u64 - 1, u64::MAX - 58];
+        let modulus = u64::MAX - 58;
+
+        for &a in values.iter() {
+            for &b in values.iter() {
+                let (a, b) = (a % modulus, b % modulus);
+                let expected = (a as u128 * b as u128 % modulus as u128) as u64;
+
+                assert_eq!(a.mul_mod(b, modulus), expected);
+                assert_eq!((a as u128).mul_mod(b as u128, modulus as u128), expected as u128);
+            }
+        }
+    }
+
+    #[test]
+    fn test_u128_arithmetic_near_max() {
+        let modulus = u128::MAX - 158;
+        let a = modulus - 1;
+
+        // (-1) * (-1) = 1, (-1) + (-1) = -2 and 0 - 1 = -1
+        assert_eq!(a.mul_mod(a, modulus), 1);
+        assert_eq!(a.add_mod(a, modulus), modulus - 2);
+        assert_eq!(0.sub_mod(1, modulus), modulus - 1);
+    }
+
+    #[test]
+    fn test_pow_mod() {
+        assert_eq!(2_u64.pow_mod(10, 1_000), 24);
+        assert_eq!(3_u32.pow_mod(0, 7), 1);
+        assert_eq!(5_u64.pow_mod(3, 1), 0);
+
+        // Fermat's little theorem for the prime 2^127 - 1
+        let p = (1_u128 << 127) - 1;
+        assert_eq!(3_u128.pow_mod(p - 1, p), 1);
+    }
+
+    #[test]
+    fn test_montgomery_matches_mul_mod() {
+        let modulus = u128::MAX - 158;
+        let montgomery = Montgomery128::new(modulus);
+
+        // R mod n, so that a * R * R^-1 = a
+        let r = (u128::MAX % modulus + 1) % modulus;
+        let values = [0, 1, 2, 3, u64::MAX as u128, 1 << 100, modulus - 1];
+
+        for &a in values.iter() {
+            for &b in values.iter() {
+                let expected = a.mul_mod(b, modulus);
+                let a_mont = a.mul_mod(r, modulus);
+                let b_mont = b.mul_mod(r, modulus);
+
+                assert_eq!(montgomery.mul(montgomery.mul(a_mont, b_mont), 1), expected);
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::get_primes_up_to;
+use crate::primes::segmented::{sieve_segment, SEGMENT_SIZE};
+use rayon::prelude::*;
+
+/// Parallel version of ``get_primes_up_to``
+///
+/// Gives exactly the same primes, sieving cache-sized segments concurrently on the rayon pool.
+pub fn get_primes_up_to_parallel(n: usize) -> Vec<u64> {
+    // u64::MAX is divisible by 3, so stopping just short of it when n + 1 overflows loses nothing
+    get_primes_in_range_parallel(0, (n as u64).saturating_add(1))
+}
+
+/// Parallel version of ``get_primes_in_range``, collecting the primes ``p`` with ``lo <= p < hi``
+///
+/// The primes up to ``sqrt(hi)`` are found once up front and shared by every segment. Each
+/// segment's primes are collected separately and joined in order.
+pub fn get_primes_in_range_parallel(lo: u64, hi: u64) -> Vec<u64> {
+    let lo = lo.max(2);
+    if lo >= hi {
+        return Vec::new();
+    }
+
+    let base_primes = get_primes_up_to((hi - 1).isqrt() as usize);
+    let n_segments = (hi - lo).div_ceil(SEGMENT_SIZE);
+
+    let segments: Vec<Vec<u64>> = (0..n_segments)
+        .into_par_iter()
+        .map_init(Vec::new, |is_prime, segment| {
+            let start = lo + segment * SEGMENT_SIZE;
+            let end = hi.min(start.saturating_add(SEGMENT_SIZE));
+
+            let mut primes = Vec::new();
+            sieve_segment(start, end, &base_primes, is_prime, &mut primes);
+            primes
+        })
+        .collect();
+
+    segments.concat()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::get_primes_in_range;
+
+    #[test]
+    fn test_matches_serial_sieve() {
+        for n in [0, 1, 2, 3, 100, 32_768, 32_769, 1_000_000] {
+            assert_eq!(get_primes_up_to_parallel(n), get_primes_up_to(n), "{n}");
+        }
+    }
+
+    #[test]
+    fn test_range_matches_serial_sieve() {
+        let lo = 1_000_000_000;
+        let hi = lo + 200_000;
+
+        assert_eq!(get_primes_in_range_parallel(lo, hi), get_primes_in_range(lo, hi));
+        assert!(get_primes_in_range_parallel(hi, lo).is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+use crate::get_primes_up_to;
+
+/// Number of integers sieved at once, sized so the working buffer stays in cache.
+pub(crate) const SEGMENT_SIZE: u64 = 1 << 15;
+
+/// Iterator over the primes in ``[lo, hi)``, sieved one cache-sized block at a time.
+///
+/// Only the primes up to ``sqrt(hi)`` and a single block are held in memory, so windows far
+/// beyond what ``get_primes_up_to`` could allocate, e.g. ``[10^12, 10^12 + 10^7)``, are cheap.
+pub struct SegmentedPrimes {
+    base_primes: Vec<u64>,
+    segment_start: u64,
+    hi: u64,
+    is_prime: Vec<bool>,
+    segment: Vec<u64>,
+    index: usize,
+}
+
+impl SegmentedPrimes {
+    pub fn new(lo: u64, hi: u64) -> SegmentedPrimes {
+        let base_limit = if hi > 1 { (hi - 1).isqrt() } else { 0 };
+
+        SegmentedPrimes {
+            base_primes: get_primes_up_to(base_limit as usize),
+            segment_start: lo.max(2),
+            hi,
+            is_prime: Vec::with_capacity(SEGMENT_SIZE as usize),
+            segment: Vec::new(),
+            index: 0,
+        }
+    }
+
+    /// Sieve the next block, replacing the buffered primes with those found in it
+    fn sieve_next_segment(&mut self) {
+        let lo = self.segment_start;
+        let hi = self.hi.min(lo.saturating_add(SEGMENT_SIZE));
+
+        self.segment.clear();
+        sieve_segment(lo, hi, &self.base_primes, &mut self.is_prime, &mut self.segment);
+        self.index = 0;
+        self.segment_start = hi;
+    }
+}
+
+/// Append the primes in ``[lo, hi)`` to ``primes``, for ``lo >= 2``
+///
+/// ``base_primes`` must hold every prime up to ``sqrt(hi)``, and ``is_prime`` is scratch space
+/// that can be reused between calls to avoid reallocating.
+pub(crate) fn sieve_segment(
+    lo: u64,
+    hi: u64,
+    base_primes: &[u64],
+    is_prime: &mut Vec<bool>,
+    primes: &mut Vec<u64>,
+) {
+    is_prime.clear();
+    is_prime.resize((hi - lo) as usize, true);
+
+    for &p in base_primes.iter() {
+        let p_squared = p * p;
+        if p_squared >= hi {
+            break;
+        }
+
+        // The first multiple of p in the block, skipping those with a smaller prime factor. Near
+        // u64::MAX there may be no multiple left to find.
+        let Some(next_multiple) = lo.div_ceil(p).checked_mul(p) else {
+            continue;
+        };
+        let first = p_squared.max(next_multiple);
+        for multiple in (first..hi).step_by(p as usize) {
+            is_prime[(multiple - lo) as usize] = false;
+        }
+    }
+
+    primes.extend(
+        is_prime
+            .iter()
+            .enumerate()
+            .filter(|(_, &is_prime)| is_prime)
+            .map(|(offset, _)| lo + offset as u64),
+    );
+}
+
+impl Iterator for SegmentedPrimes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        while self.index >= self.segment.len() {
+            if self.segment_start >= self.hi {
+                return None;
+            }
+            self.sieve_next_segment();
+        }
+
+        let prime = self.segment[self.index];
+        self.index += 1;
+        Some(prime)
+    }
+}
+
+/// Iterate over the primes ``p`` with ``lo <= p < hi``
+pub fn primes_in_range(lo: u64, hi: u64) -> SegmentedPrimes {
+    SegmentedPrimes::new(lo, hi)
+}
+
+/// Collect the primes ``p`` with ``lo <= p < hi``
+///
+/// Gives the same primes as ``get_primes_up_to`` where the two overlap, without allocating a
+/// flag for every integer below ``hi``.
+pub fn get_primes_in_range(lo: u64, hi: u64) -> Vec<u64> {
+    primes_in_range(lo, hi).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_matches_simple_sieve() {
+        let expected = get_primes_up_to(200_000);
+        let actual = get_primes_in_range(0, 200_001);
+
+        assert_eq!(actual, expected)
+    }
+
+    #[test]
+    fn test_windows_match_simple_sieve() {
+        let all_primes = get_primes_up_to(100_000);
+
+        for (lo, hi) in [(0, 2), (0, 3), (2, 3), (10, 30), (32_760, 32_780), (65_000, 99_999)] {
+            let expected: Vec<_> = all_primes
+                .iter()
+                .copied()
+                .filter(|&p| lo <= p && p < hi)
+                .collect();
+
+            assert_eq!(get_primes_in_range(lo, hi), expected, "[{lo}, {hi})");
+        }
+    }
+
+    #[test]
+    fn test_empty_ranges() {
+        assert!(get_primes_in_range(0, 0).is_empty());
+        assert!(get_primes_in_range(100, 50).is_empty());
+        assert!(get_primes_in_range(24, 29).is_empty());
+    }
+
+    #[test]
+    fn test_window_beyond_memory() {
+        let lo = 1_000_000_000_000;
+        let hi = lo + 1_000;
+        let base_primes = get_primes_up_to(1_000_000);
+
+        let is_prime = |n: u64| base_primes.iter().all(|&p| !n.is_multiple_of(p));
+        let expected: Vec<_> = (lo..hi).filter(|&n| is_prime(n)).collect();
+
+        assert_eq!(get_primes_in_range(lo, hi), expected);
+        assert_eq!(expected.first(), Some(&1_000_000_000_039));
+    }
+
+    #[test]
+    fn test_segment_at_u64_max() {
+        // Most of these primes have no multiple between lo and u64::MAX, so looking for one must
+        // not overflow. Without every prime up to 2^32 only the small factors are crossed off.
+        let (lo, hi) = (u64::MAX - 100, u64::MAX);
+        let base_primes = get_primes_up_to(1_000);
+
+        let mut primes = Vec::new();
+        sieve_segment(lo, hi, &base_primes, &mut Vec::new(), &mut primes);
+
+        let expected: Vec<_> = (lo..hi)
+            .filter(|&n| base_primes.iter().all(|&p| !n.is_multiple_of(p)))
+            .collect();
+        assert_eq!(primes, expected);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::factor::Factorization;
+use num::traits::PrimInt;
+
+/// Table of the smallest prime factor of every integer up to a limit.
+///
+/// Once built, any ``n`` in the table can be broken down in O(log n) by repeatedly dividing out its
+/// smallest prime factor, which makes factorising every number in a range far cheaper than trial
+/// division. The entry type ``S`` sets the memory use: the default ``u32`` halves the table
+/// compared to ``u64``, giving 400 MB for a limit of 10^8.
+pub struct SpfSieve<S = u32> {
+    smallest_factor: Vec<S>,
+}
+
+impl<S: PrimInt> SpfSieve<S> {
+    /// Build the table for all ``n <= limit``
+    pub fn new(limit: usize) -> SpfSieve<S> {
+        assert!(
+            S::from(limit).is_some(),
+            "a limit of {limit} doesn't fit in the sieve's storage type"
+        );
+
+        let mut smallest_factor = vec![S::zero(); limit + 1];
+
+        for i in 2..=limit {
+            if smallest_factor[i] != S::zero() {
+                continue;
+            }
+            let prime = S::from(i).unwrap();
+            smallest_factor[i] = prime;
+
+            for j in (i.saturating_mul(i)..=limit).step_by(i) {
+                if smallest_factor[j] == S::zero() {
+                    smallest_factor[j] = prime;
+                }
+            }
+        }
+
+        SpfSieve { smallest_factor }
+    }
+
+    /// The largest value covered by the table
+    pub fn limit(&self) -> u64 {
+        (self.smallest_factor.len() - 1) as u64
+    }
+
+    /// The smallest prime dividing ``n``, for ``2 <= n <= limit``
+    pub fn smallest_prime_factor(&self, n: u64) -> u64 {
+        assert!(
+            (2..=self.limit()).contains(&n),
+            "{n} is outside the sieve range [2, {}]",
+            self.limit()
+        );
+        self.smallest_factor[n as usize].to_u64().unwrap()
+    }
+
+    pub fn is_prime(&self, n: u64) -> bool {
+        n >= 2 && self.smallest_prime_factor(n) == n
+    }
+
+    /// Iterate over the ``(prime, exponent)`` pairs of ``n`` in ascending order
+    fn prime_powers(&self, n: u64) -> impl Iterator<Item = (u64, u32)> + '_ {
+        let mut n = n;
+
+        std::iter::from_fn(move || {
+            if n <= 1 {
+                return None;
+            }
+
+            let prime = self.smallest_prime_factor(n);
+            let mut exponent = 0;
+            while n.is_multiple_of(prime) {
+                n /= prime;
+                exponent += 1;
+            }
+            Some((prime, exponent))
+        })
+    }
+
+    /// The prime factorisation of ``1 <= n <= limit``
+    pub fn factorize(&self, n: u64) -> Factorization<u64> {
+        assert!(n != 0, "0 has no prime factorisation");
+        let primes = self
+            .prime_powers(n)
+            .flat_map(|(p, e)| std::iter::repeat_n(p, e as usize))
+            .collect();
+
+        Factorization::from_primes(primes)
+    }
+
+    /// The distinct primes dividing ``n``, in ascending order
+    pub fn distinct_prime_factors(&self, n: u64) -> Vec<u64> {
+        self.prime_powers(n).map(|(p, _)| p).collect()
+    }
+
+    /// The prime factors raised to their powers, e.g. 644 = 2^2 * 7 * 23 gives [4, 7, 23]
+    ///
+    /// These are pairwise co-prime and multiply back to ``n``.
+    pub fn prime_power_parts(&self, n: u64) -> Vec<u64> {
+        self.prime_powers(n).map(|(p, e)| p.pow(e)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::factorize;
+
+    #[test]
+    fn test_matches_factorize() {
+        let sieve: SpfSieve = SpfSieve::new(10_000);
+
+        for n in 1..=10_000 {
+            assert_eq!(sieve.factorize(n), factorize(n), "{n}");
+        }
+    }
+
+    #[test]
+    fn test_storage_types_agree() {
+        let narrow: SpfSieve<u32> = SpfSieve::new(5_000);
+        let wide: SpfSieve<u64> = SpfSieve::new(5_000);
+
+        for n in 2..=5_000 {
+            assert_eq!(narrow.smallest_prime_factor(n), wide.smallest_prime_factor(n));
+        }
+    }
+
+    #[test]
+    fn test_prime_power_parts() {
+        let sieve: SpfSieve = SpfSieve::new(1_000);
+
+        assert_eq!(sieve.prime_power_parts(644), [4, 7, 23]);
+        assert_eq!(sieve.prime_power_parts(646), [2, 17, 19]);
+        assert_eq!(sieve.prime_power_parts(1), []);
+        assert_eq!(sieve.distinct_prime_factors(644), [2, 7, 23]);
+        assert_eq!(sieve.distinct_prime_factors(512), [2]);
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let sieve: SpfSieve = SpfSieve::new(100);
+        let primes: Vec<_> = (0..=100).filter(|&n| sieve.is_prime(n)).collect();
+
+        assert_eq!(primes, crate::get_primes_up_to(100));
+    }
+
+    #[test]
+    #[should_panic(expected = "0 has no prime factorisation")]
+    fn test_factorize_zero() {
+        SpfSieve::<u32>::new(100).factorize(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_limit_too_large_for_storage() {
+        SpfSieve::<u8>::new(256);
+    }
+}
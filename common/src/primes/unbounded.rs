@@ -0,0 +1,133 @@
+use crate::primes::segmented::SegmentedPrimes;
+use crate::primes::wheel::PrimeSieve;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Size of the first window sieved by ``Primes``
+const INITIAL_WINDOW: u64 = 1 << 16;
+
+/// Smallest limit sieved by a ``PrimeCache``
+const INITIAL_CACHE_LIMIT: u64 = 1 << 16;
+
+/// Iterator over every prime, without an upper bound.
+///
+/// The primes are sieved a window at a time as the iterator is consumed, and each window is as
+/// large as everything sieved so far, so the base primes are only recomputed a logarithmic number
+/// of times.
+pub struct Primes {
+    window: SegmentedPrimes,
+    window_end: u64,
+}
+
+impl Primes {
+    pub fn new() -> Primes {
+        Primes {
+            window: SegmentedPrimes::new(0, INITIAL_WINDOW),
+            window_end: INITIAL_WINDOW,
+        }
+    }
+}
+
+impl Default for Primes {
+    fn default() -> Self {
+        Primes::new()
+    }
+}
+
+impl Iterator for Primes {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            if let Some(prime) = self.window.next() {
+                return Some(prime);
+            }
+
+            let lo = self.window_end;
+            self.window_end = lo.saturating_mul(2);
+            self.window = SegmentedPrimes::new(lo, self.window_end);
+        }
+    }
+}
+
+/// Shared primality lookup that grows its sieve on demand.
+///
+/// Useful for open-ended searches, like ``(3..).find(..)``, where the largest value queried isn't
+/// known ahead of time. Clones share the same sieve, so one handle can be passed around freely and
+/// the sieving is only done once. Each time a query falls beyond the sieve it is rebuilt to at
+/// least double the limit, keeping the total cost linear in the largest value queried.
+#[derive(Clone)]
+pub struct PrimeCache {
+    sieve: Rc<RefCell<PrimeSieve>>,
+}
+
+impl PrimeCache {
+    pub fn new() -> PrimeCache {
+        PrimeCache::with_limit(INITIAL_CACHE_LIMIT)
+    }
+
+    /// Create the cache with the primes up to ``limit`` already sieved
+    pub fn with_limit(limit: u64) -> PrimeCache {
+        PrimeCache {
+            sieve: Rc::new(RefCell::new(PrimeSieve::new(limit))),
+        }
+    }
+
+    /// The largest value currently sieved
+    pub fn limit(&self) -> u64 {
+        self.sieve.borrow().limit()
+    }
+
+    pub fn is_prime(&self, n: u64) -> bool {
+        let limit = self.limit();
+        if n > limit {
+            let new_limit = n.max(limit.saturating_mul(2));
+            *self.sieve.borrow_mut() = PrimeSieve::new(new_limit);
+        }
+
+        self.sieve.borrow().is_prime(n)
+    }
+}
+
+impl Default for PrimeCache {
+    fn default() -> Self {
+        PrimeCache::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::get_primes_up_to;
+
+    #[test]
+    fn test_primes_match_sieve() {
+        // Enough to cross several window boundaries
+        let expected = get_primes_up_to(600_000);
+        let actual: Vec<_> = Primes::new().take(expected.len()).collect();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_nth_prime() {
+        assert_eq!(Primes::new().nth(10_000), Some(104_743));
+    }
+
+    #[test]
+    fn test_cache_grows() {
+        let cache = PrimeCache::with_limit(10);
+        let shared = cache.clone();
+
+        assert!(cache.is_prime(7));
+        assert_eq!(cache.limit(), 10);
+
+        assert!(shared.is_prime(1_000_003));
+        assert!(!shared.is_prime(1_000_001));
+        assert!(cache.limit() >= 1_000_003);
+
+        let primes = get_primes_up_to(20_000);
+        let found: Vec<_> = (0..=20_000).filter(|&n| cache.is_prime(n)).collect();
+        assert_eq!(found, primes);
+    }
+}
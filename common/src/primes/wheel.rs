@@ -0,0 +1,276 @@
+use std::ops::{Bound, RangeBounds};
+
+/// The primes dividing 30, which the wheel skips over
+const SMALL_PRIMES: [u64; 3] = [2, 3, 5];
+
+/// Residues mod 30 that are coprime to 30, one per bit of each byte in the sieve
+const WHEEL: [u64; 8] = [1, 7, 11, 13, 17, 19, 23, 29];
+
+/// Bit position of each residue mod 30, ``None`` where the residue shares a factor with 30
+const BIT_INDEX: [Option<u8>; 30] = {
+    let mut index = [None; 30];
+    let mut bit = 0;
+    while bit < WHEEL.len() {
+        index[WHEEL[bit] as usize] = Some(bit as u8);
+        bit += 1;
+    }
+    index
+};
+
+/// Sieve of Eratosthenes stored as a mod-30 wheel bitset.
+///
+/// Only the eight residues coprime to 30 can be prime (other than 2, 3 and 5), so each byte holds
+/// the primality of a block of 30 integers. This takes ~1/27 of the memory of a ``Vec<bool>`` and
+/// far less than a hash set of the primes, while answering ``is_prime`` with a single bit lookup.
+pub struct PrimeSieve {
+    limit: u64,
+    bits: Vec<u8>,
+}
+
+impl PrimeSieve {
+    /// Sieve all the primes up to and including ``limit``
+    pub fn new(limit: u64) -> PrimeSieve {
+        let n_bytes = (limit / 30 + 1) as usize;
+        let mut sieve = PrimeSieve {
+            limit,
+            bits: vec![0xFF; n_bytes],
+        };
+
+        // 1 is on the wheel but isn't prime
+        sieve.bits[0] &= !1;
+        sieve.clear_beyond_limit();
+
+        let sqrt_limit = limit.isqrt();
+        for p_byte in 0..=(sqrt_limit / 30) as usize {
+            for (p_bit, &residue) in WHEEL.iter().enumerate() {
+                let p = p_byte as u64 * 30 + residue;
+                if p > sqrt_limit || sieve.bits[p_byte] & (1 << p_bit) == 0 {
+                    continue;
+                }
+
+                // Cross off p * q for every wheel candidate q >= p, as smaller q have already
+                // removed their multiples of p.
+                let (mut q_byte, mut q_bit) = (p_byte as u64, p_bit);
+                loop {
+                    let multiple = p * (q_byte * 30 + WHEEL[q_bit]);
+                    if multiple > limit {
+                        break;
+                    }
+                    sieve.clear(multiple);
+
+                    q_bit += 1;
+                    if q_bit == WHEEL.len() {
+                        q_bit = 0;
+                        q_byte += 1;
+                    }
+                }
+            }
+        }
+
+        sieve
+    }
+
+    /// Rebuild a sieve from its limit and bitset, as given by ``as_bytes``
+    ///
+    /// Returns ``None`` if the bitset is the wrong length for the limit.
+    pub(crate) fn from_raw_parts(limit: u64, bits: Vec<u8>) -> Option<PrimeSieve> {
+        if bits.len() as u64 != limit / 30 + 1 {
+            return None;
+        }
+
+        let mut sieve = PrimeSieve { limit, bits };
+        sieve.clear_beyond_limit();
+        Some(sieve)
+    }
+
+    /// The raw wheel bitset
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.bits
+    }
+
+    /// Shrink the sieve to cover only the values up to ``limit``
+    pub(crate) fn truncate(&mut self, limit: u64) {
+        if limit < self.limit {
+            self.limit = limit;
+            self.bits.truncate((limit / 30 + 1) as usize);
+            self.clear_beyond_limit();
+        }
+    }
+
+    /// Unset the bits in the final byte that lie past the limit
+    fn clear_beyond_limit(&mut self) {
+        let last_byte = self.bits.len() - 1;
+        for (bit, &residue) in WHEEL.iter().enumerate() {
+            if last_byte as u64 * 30 + residue > self.limit {
+                self.bits[last_byte] &= !(1 << bit);
+            }
+        }
+    }
+
+    /// The largest value covered by the sieve
+    pub fn limit(&self) -> u64 {
+        self.limit
+    }
+
+    fn clear(&mut self, n: u64) {
+        if let Some(bit) = BIT_INDEX[(n % 30) as usize] {
+            self.bits[(n / 30) as usize] &= !(1 << bit);
+        }
+    }
+
+    /// Test if ``n`` is prime, panicking if ``n`` is beyond the sieved limit
+    pub fn is_prime(&self, n: u64) -> bool {
+        assert!(
+            n <= self.limit,
+            "{n} is beyond the sieve limit of {}",
+            self.limit
+        );
+
+        match BIT_INDEX[(n % 30) as usize] {
+            Some(bit) => self.bits[(n / 30) as usize] & (1 << bit) != 0,
+            None => SMALL_PRIMES.contains(&n),
+        }
+    }
+
+    /// Iterate over all the primes in the sieve in ascending order
+    pub fn primes(&self) -> impl Iterator<Item = u64> + '_ {
+        self.primes_in(..)
+    }
+
+    /// Iterate over the primes in ``range`` in ascending order
+    ///
+    /// Values beyond the sieve limit are ignored.
+    pub fn primes_in<R: RangeBounds<u64>>(&self, range: R) -> impl Iterator<Item = u64> + '_ {
+        let lo = match range.start_bound() {
+            Bound::Included(&lo) => lo,
+            Bound::Excluded(&lo) => lo.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        // Use an exclusive upper bound so that empty ranges stay empty
+        let hi = match range.end_bound() {
+            Bound::Included(&hi) => hi.saturating_add(1),
+            Bound::Excluded(&hi) => hi,
+            Bound::Unbounded => u64::MAX,
+        }
+        .min(self.limit + 1);
+
+        let small = SMALL_PRIMES
+            .into_iter()
+            .filter(move |p| (lo..hi).contains(p));
+
+        let bytes = if lo < hi {
+            (lo / 30) as usize..((hi - 1) / 30 + 1) as usize
+        } else {
+            0..0
+        };
+        let wheel = bytes
+            .flat_map(move |byte| {
+                let mask = self.bits[byte];
+                WHEEL
+                    .iter()
+                    .enumerate()
+                    .filter(move |(bit, _)| mask & (1 << bit) != 0)
+                    .map(move |(_, &residue)| byte as u64 * 30 + residue)
+            })
+            .filter(move |p| (lo..hi).contains(p));
+
+        small.chain(wheel)
+    }
+
+    /// The number of primes up to the sieve limit
+    pub fn count(&self) -> usize {
+        let n_small = SMALL_PRIMES.iter().filter(|&&p| p <= self.limit).count();
+        let n_wheel: u32 = self.bits.iter().map(|byte| byte.count_ones()).sum();
+
+        n_small + n_wheel as usize
+    }
+
+    /// The ``k``th prime (counting from zero), if it is within the sieve limit
+    pub fn nth(&self, k: usize) -> Option<u64> {
+        let n_small = SMALL_PRIMES.iter().filter(|&&p| p <= self.limit).count();
+        if k < n_small {
+            return Some(SMALL_PRIMES[k]);
+        }
+
+        // Skip whole bytes by their population count, then find the bit within the byte
+        let mut remaining = (k - n_small) as u32;
+        for (byte, &mask) in self.bits.iter().enumerate() {
+            let n_primes = mask.count_ones();
+            if remaining < n_primes {
+                let bit = (0..WHEEL.len())
+                    .filter(|bit| mask & (1 << bit) != 0)
+                    .nth(remaining as usize)
+                    .unwrap();
+                return Some(byte as u64 * 30 + WHEEL[bit]);
+            }
+            remaining -= n_primes;
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::get_primes_up_to;
+
+    #[test]
+    fn test_matches_simple_sieve() {
+        for limit in [0, 1, 2, 3, 4, 5, 6, 7, 29, 30, 31, 59, 60, 61, 1_000, 100_003] {
+            let expected = get_primes_up_to(limit as usize);
+            let sieve = PrimeSieve::new(limit);
+
+            assert_eq!(sieve.primes().collect::<Vec<_>>(), expected, "limit {limit}");
+            assert_eq!(sieve.count(), expected.len(), "limit {limit}");
+        }
+    }
+
+    #[test]
+    fn test_is_prime() {
+        let limit = 10_000;
+        let sieve = PrimeSieve::new(limit);
+        let primes = get_primes_up_to(limit as usize);
+
+        for n in 0..=limit {
+            assert_eq!(sieve.is_prime(n), primes.binary_search(&n).is_ok(), "{n}");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_is_prime_beyond_limit() {
+        PrimeSieve::new(100).is_prime(101);
+    }
+
+    #[test]
+    fn test_primes_in() {
+        let sieve = PrimeSieve::new(1_000);
+        let primes = get_primes_up_to(1_000);
+
+        for (lo, hi) in [(0, 10), (3, 7), (29, 31), (30, 60), (500, 1_000), (990, 2_000), (8, 3)] {
+            let expected: Vec<_> = primes
+                .iter()
+                .copied()
+                .filter(|&p| lo <= p && p <= hi)
+                .collect();
+
+            assert_eq!(sieve.primes_in(lo..=hi).collect::<Vec<_>>(), expected, "[{lo}, {hi}]");
+        }
+
+        assert_eq!(sieve.primes_in(2..3).collect::<Vec<_>>(), [2]);
+        assert_eq!(sieve.primes_in(..0).count(), 0);
+    }
+
+    #[test]
+    fn test_nth() {
+        let sieve = PrimeSieve::new(200_000);
+        let primes = get_primes_up_to(200_000);
+
+        for (k, &p) in primes.iter().enumerate().step_by(97) {
+            assert_eq!(sieve.nth(k), Some(p));
+        }
+        assert_eq!(sieve.nth(primes.len()), None);
+        assert_eq!(sieve.nth(10_000), Some(104_743));
+    }
+}
@@ -5,7 +5,6 @@
 //
 // Which starting number under one million produces the longest chain?
 
-use std::collections::HashMap;
 use std::time::Instant;
 use rayon::prelude::*;
 use ahash::AHashMap;
@@ -45,7 +44,7 @@ fn main() {
 
     println!("Serial version (uncached):");
     let start_time = Instant::now();
-    let (max_steps, starting_value) = lookup_uncached(range_end);
+    let (max_steps, _) = lookup_uncached(range_end);
     let elapsed_time = start_time.elapsed();
 
     println!("Max steps: {}", max_steps);
@@ -54,7 +53,7 @@ fn main() {
 
     println!("Parallel version (uncached):");
     let start_time = Instant::now();
-    let (max_steps, starting_value) = par_lookup_uncached(range_end);
+    let (max_steps, _) = par_lookup_uncached(range_end);
     let elapsed_time = start_time.elapsed();
 
     println!("Max steps: {}", max_steps);
@@ -103,7 +102,7 @@ fn par_lookup(range_end: u64) -> (u64, u64) {
             }
         })
         .reduce(|| (1, 1, starting_dict()),
-          |(max_steps, max_starting_value, mut step_dict), (steps, starting_value, local_dict)|{
+          |(max_steps, max_starting_value, step_dict), (steps, starting_value, local_dict)|{
             // Results from fold must be reduced into a single result
             if steps > max_steps {
                 (steps, starting_value, local_dict)
@@ -197,7 +196,6 @@ fn get_sequence_length_uncached(val: u64)  -> u64 {
 
         num = 3 * num + 1;
     }
-    steps
 }
 
 #[cfg(test)]
@@ -1,7 +1,8 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use itertools::Itertools;
 use problem_23::proper_divisors::{
-    calculate_proper_divisors, calculate_proper_divisors_doubled, calculate_proper_divisors_sqrt,
+    calculate_proper_divisors, calculate_proper_divisors_doubled, calculate_proper_divisors_sorted,
+    calculate_proper_divisors_sqrt,
 };
 
 use problem_23::get_abundant_numbers::brute_force::collect_abundant_numbers_parallel;
@@ -21,6 +22,10 @@ fn full_proper_divisors(c: &mut Criterion) {
     c.bench_function("sqrt divisors alt", |b| {
         b.iter(|| calculate_proper_divisors_doubled(black_box(value)))
     });
+
+    c.bench_function("sorted divisors", |b| {
+        b.iter(|| calculate_proper_divisors_sorted(black_box(value)).collect::<Vec<_>>())
+    });
 }
 
 fn get_abundant_numbers_bench(c: &mut Criterion) {
@@ -1,16 +1,23 @@
-use crate::get_abundant_numbers::{get_sum_type, SumType};
+use crate::get_abundant_numbers::{sum_type_from_divisor_sum, SumType};
+use common::divisor_sums_parallel;
 
 use rayon::prelude::*;
 
 /// Brute force approach to finding abundant numbers
 ///
-/// This function will find all abundant numbers up to and including `max_val`
-/// As this approach can be parallelized, with enough cores, this approach can be
-/// faster than the multiplicative approach.
+/// This function will find all abundant numbers up to and including `max_val`, checking every
+/// number in turn rather than building on the abundant numbers already found.
+/// The divisor sums are sieved in parallel chunks, and then each number is classified
+/// independently, so both steps spread across all of the available cores. The sums are kept as
+/// ``u32``, which holds them up to a ``max_val`` of 10^8.
 pub fn collect_abundant_numbers_parallel(max_val: u64) -> Vec<u64> {
+    let sigma: Vec<u32> = divisor_sums_parallel(max_val as usize);
+
     (12..=max_val)
         .into_par_iter()
-        .filter(|v| get_sum_type(*v) == SumType::Abundant)
+        .filter(|&v| {
+            sum_type_from_divisor_sum(v, sigma[v as usize] as u64 - v) == SumType::Abundant
+        })
         .collect()
 }
 
@@ -25,4 +32,17 @@ mod test {
 
         assert_eq!(abundant_numbers, abundant_numbers_expected)
     }
+
+    #[test]
+    fn test_matches_multiplicative_approach() {
+        use crate::get_abundant_numbers::multiplicative_approach::collect_abundant_numbers;
+
+        for max_val in [0, 11, 12, 20_161, 100_000] {
+            assert_eq!(
+                collect_abundant_numbers_parallel(max_val),
+                collect_abundant_numbers(max_val),
+                "{max_val}"
+            );
+        }
+    }
 }
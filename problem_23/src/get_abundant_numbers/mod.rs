@@ -1,7 +1,8 @@
 pub mod brute_force;
 pub mod multiplicative_approach;
 
-use crate::proper_divisors::calculate_proper_divisors_doubled;
+use crate::proper_divisors::calculate_proper_divisors_sorted;
+use num::rational::Ratio;
 
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub enum SumType {
@@ -11,9 +12,19 @@ pub enum SumType {
 }
 
 pub fn get_sum_type(num: u64) -> SumType {
+    // 0 has no divisor sum, so give it the same placeholder as ``classify_numbers``
+    if num == 0 {
+        return SumType::Deficient;
+    }
+
     // We might make this more efficient by breaking early if the sum is greater than num?
-    let divisor_sum = calculate_proper_divisors_doubled(num).iter().sum::<u64>();
+    let divisor_sum = calculate_proper_divisors_sorted(num).sum::<u64>();
+
+    sum_type_from_divisor_sum(num, divisor_sum)
+}
 
+/// Classify ``num`` given the sum of its proper divisors
+pub fn sum_type_from_divisor_sum(num: u64, divisor_sum: u64) -> SumType {
     if num == divisor_sum {
         SumType::Perfect
     } else if divisor_sum > num {
@@ -23,6 +34,110 @@ pub fn get_sum_type(num: u64) -> SumType {
     }
 }
 
+/// Finer classification of a number by its divisors, extending ``SumType``
+///
+/// Built with ``get_extended_sum_type`` for a single number, or ``classify_numbers_extended`` for
+/// a whole range.
+#[derive(PartialEq, Debug, Copy, Clone)]
+pub struct ExtendedSumType {
+    /// The number being classified
+    pub num: u64,
+    pub sum_type: SumType,
+    /// The abundancy index σ(n) / n, which is 2 for perfect numbers
+    pub abundancy: Ratio<u64>,
+    /// Some subset of the proper divisors sums to the number
+    pub semiperfect: bool,
+    /// Abundant, with every proper divisor deficient
+    pub primitive_abundant: bool,
+}
+
+impl ExtendedSumType {
+    /// Placeholder for 0, which has no divisor sum
+    pub(crate) fn zero() -> ExtendedSumType {
+        ExtendedSumType {
+            num: 0,
+            sum_type: SumType::Deficient,
+            abundancy: Ratio::from_integer(0),
+            semiperfect: false,
+            primitive_abundant: false,
+        }
+    }
+
+    /// The ``k`` with σ(n) = k n, if there is one; 2 for perfect numbers and 3 for 120
+    pub fn multiply_perfect(&self) -> Option<u64> {
+        self.abundancy.is_integer().then(|| self.abundancy.to_integer())
+    }
+
+    /// Abundant, but with no subset of the proper divisors summing to the number
+    pub fn is_weird(&self) -> bool {
+        self.sum_type == SumType::Abundant && !self.semiperfect
+    }
+
+    /// σ(n) = 2n + 1; none are known, but none have been ruled out either
+    pub fn is_quasi_perfect(&self) -> bool {
+        // The reduced ratio has lost n, so σ(n) has to be rebuilt from it
+        self.abundancy * self.num == Ratio::from_integer(2 * self.num + 1)
+    }
+}
+
+pub fn get_extended_sum_type(num: u64) -> ExtendedSumType {
+    if num == 0 {
+        return ExtendedSumType::zero();
+    }
+
+    let divisors: Vec<u64> = calculate_proper_divisors_sorted(num).collect();
+    let divisor_sum = divisors.iter().sum::<u64>();
+    let sum_type = sum_type_from_divisor_sum(num, divisor_sum);
+
+    let semiperfect = match sum_type {
+        SumType::Perfect => true,
+        SumType::Abundant => is_subset_sum(&divisors, num),
+        SumType::Deficient => false,
+    };
+    let primitive_abundant = sum_type == SumType::Abundant
+        && divisors
+            .iter()
+            .all(|&d| get_sum_type(d) == SumType::Deficient);
+
+    ExtendedSumType {
+        num,
+        sum_type,
+        abundancy: Ratio::new(divisor_sum + num, num),
+        semiperfect,
+        primitive_abundant,
+    }
+}
+
+/// Check if some subset of the ascending ``divisors`` sums to ``target``
+///
+/// A depth-first search taking the largest divisors first, which gets close to the target
+/// quickly. A branch is dropped as soon as the divisors left can't reach the target.
+pub(crate) fn is_subset_sum(divisors: &[u64], target: u64) -> bool {
+    // prefix_sums[i] is the sum of the smallest i divisors
+    let prefix_sums: Vec<u64> = std::iter::once(0)
+        .chain(divisors.iter().scan(0, |sum, &d| {
+            *sum += d;
+            Some(*sum)
+        }))
+        .collect();
+
+    fn search(divisors: &[u64], prefix_sums: &[u64], target: u64) -> bool {
+        let n = divisors.len();
+        if target == 0 || prefix_sums[n] == target {
+            return true;
+        }
+        if prefix_sums[n] < target {
+            return false;
+        }
+
+        let largest = divisors[n - 1];
+        (largest <= target && search(&divisors[..n - 1], prefix_sums, target - largest))
+            || search(&divisors[..n - 1], prefix_sums, target)
+    }
+
+    search(divisors, &prefix_sums, target)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -52,9 +167,67 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_extended_sum_type() {
+        let twelve = get_extended_sum_type(12);
+        assert_eq!(twelve.sum_type, SumType::Abundant);
+        assert_eq!(twelve.abundancy, Ratio::new(7, 3));
+        assert!(twelve.semiperfect, "12 = 1 + 2 + 3 + 6");
+        assert!(!twelve.primitive_abundant, "12 has the perfect divisor 6");
+
+        let seventy = get_extended_sum_type(70);
+        assert!(seventy.is_weird(), "70 should be weird");
+        assert!(seventy.primitive_abundant, "70 should be primitive abundant");
+
+        assert_eq!(get_extended_sum_type(28).multiply_perfect(), Some(2));
+        assert_eq!(get_extended_sum_type(120).multiply_perfect(), Some(3));
+        assert_eq!(get_extended_sum_type(30_240).multiply_perfect(), Some(4));
+        assert_eq!(get_extended_sum_type(1).multiply_perfect(), Some(1));
+        assert_eq!(get_extended_sum_type(12).multiply_perfect(), None);
+
+        // 7/3 and 3/1 both have the form (2k + 1)/k without σ(n) being 2n + 1
+        assert!(!twelve.is_quasi_perfect());
+        assert!(!get_extended_sum_type(120).is_quasi_perfect());
+    }
+
+    #[test]
+    fn test_no_quasi_perfect_numbers() {
+        let quasi_perfect: Vec<_> = (1..10_000)
+            .filter(|&num| get_extended_sum_type(num).is_quasi_perfect())
+            .collect();
+
+        assert_eq!(quasi_perfect, []);
+    }
+
+    #[test]
+    fn test_weird_numbers() {
+        let weird: Vec<_> = (1..10_000)
+            .filter(|&num| get_extended_sum_type(num).is_weird())
+            .collect();
+
+        assert_eq!(weird, [70, 836, 4030, 5830, 7192, 7912, 9272]);
+    }
+
+    #[test]
+    fn test_primitive_abundant_numbers() {
+        let primitive: Vec<_> = (1..=104)
+            .filter(|&num| get_extended_sum_type(num).primitive_abundant)
+            .collect();
+
+        assert_eq!(primitive, [20, 70, 88, 104]);
+    }
+
+    #[test]
+    fn test_is_subset_sum() {
+        assert!(is_subset_sum(&[1, 2, 4, 5, 10], 20));
+        assert!(!is_subset_sum(&[1, 2, 5, 7, 10, 14, 35], 70));
+        assert!(is_subset_sum(&[], 0));
+        assert!(!is_subset_sum(&[3, 5], 4));
+    }
+
     #[test]
     fn test_deficient_numbers() {
-        let nums = [1, 16];
+        let nums = [0, 1, 16];
 
         for num in nums {
             assert_eq!(
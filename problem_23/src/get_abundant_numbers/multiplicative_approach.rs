@@ -1,8 +1,13 @@
-use crate::get_abundant_numbers::get_sum_type;
-/// We make use of the fact that the product of an abundant number is also abundant
-/// The product of a perfect number is abundant (except the perfect number itself)
-/// This allows us to reduce the number of numbers we need to check
-use crate::get_abundant_numbers::SumType;
+/// Classify a whole range of numbers at once from a sieve of their divisor sums
+///
+/// Rather than finding the divisors of each number in turn, every divisor is added to all of its
+/// multiples up front, which makes classifying every number up to ``max_val`` O(n log n).
+use crate::get_abundant_numbers::{
+    is_subset_sum, sum_type_from_divisor_sum, ExtendedSumType, SumType,
+};
+use crate::proper_divisors::calculate_proper_divisors_sorted;
+use common::divisor_sums;
+use num::rational::Ratio;
 
 /// Generate a vector of abundant numbers up to max_val
 pub fn collect_abundant_numbers(max_val: u64) -> Vec<u64> {
@@ -23,47 +28,76 @@ pub fn collect_abundant_numbers(max_val: u64) -> Vec<u64> {
 ///
 /// For an index i, the value at i is the SumType for i
 /// This is poorly defined for i = 0, so we just give it a value
+/// The divisor sums are sieved as ``u32``, which holds them for ``max_val`` up to 10^8.
 pub fn classify_numbers(max_val: usize) -> Vec<SumType> {
-    // This approach isn't as performant as expected, with the single threaded brute force
-    // approach being only 2-3x slower than this approach.
-    //
-    // Possibly, this is due to the fact that we only remove a small fraction of numbers
-    // from the list of numbers to check, and the overhead of branching is too high.
+    let sigma: Vec<u32> = divisor_sums(max_val);
 
-    let mut numbers: Vec<SumType> = vec![SumType::Deficient; max_val + 1];
+    let mut numbers: Vec<SumType> = sigma
+        .iter()
+        .enumerate()
+        .map(|(num, &sigma)| sum_type_from_divisor_sum(num as u64, sigma as u64 - num as u64))
+        .collect();
 
-    for num in 1..=max_val {
-        let selection = numbers[num];
+    // Convenient to have 0 in the array, so give some arbitrary value
+    numbers[0] = SumType::Deficient;
+    numbers
+}
 
-        if selection != SumType::Deficient {
-            continue;
-        }
+/// Generate a vector of ExtendedSumType for each number up to max_val
+///
+/// As with ``classify_numbers``, the value at i is for i, and 0 is given a placeholder. Any
+/// multiple of a semiperfect number is semiperfect too, as is any multiple of a perfect or abundant
+/// number, so these are marked off as the range is swept. This leaves the subset sum search for
+/// the few abundant numbers without a semiperfect divisor.
+pub fn classify_numbers_extended(max_val: usize) -> Vec<ExtendedSumType> {
+    let sigma: Vec<u64> = divisor_sums(max_val);
+
+    let mut numbers = vec![ExtendedSumType::zero(); max_val + 1];
+    let mut has_non_deficient_divisor = vec![false; max_val + 1];
+    let mut has_semiperfect_divisor = vec![false; max_val + 1];
+
+    for num in 1..=max_val {
+        let divisor_sum = sigma[num] - num as u64;
+        let sum_type = sum_type_from_divisor_sum(num as u64, divisor_sum);
 
-        let num_type = get_sum_type(num as u64);
-        match num_type {
+        let semiperfect = match sum_type {
+            SumType::Perfect => true,
             SumType::Abundant => {
-                for x in (num..=max_val).step_by(num) {
-                    numbers[x] = SumType::Abundant
+                has_semiperfect_divisor[num] || {
+                    let divisors: Vec<_> = calculate_proper_divisors_sorted(num as u64).collect();
+                    is_subset_sum(&divisors, num as u64)
                 }
             }
-            SumType::Perfect => {
-                numbers[num] = SumType::Perfect;
-                for x in ((num * 2)..=max_val).step_by(num) {
-                    numbers[x] = SumType::Abundant
-                }
+            SumType::Deficient => false,
+        };
+
+        if semiperfect {
+            for x in ((num * 2)..=max_val).step_by(num) {
+                has_semiperfect_divisor[x] = true;
             }
-            SumType::Deficient => {}
         }
+        if sum_type != SumType::Deficient {
+            for x in ((num * 2)..=max_val).step_by(num) {
+                has_non_deficient_divisor[x] = true;
+            }
+        }
+
+        numbers[num] = ExtendedSumType {
+            num: num as u64,
+            sum_type,
+            abundancy: Ratio::new(sigma[num], num as u64),
+            semiperfect,
+            primitive_abundant: sum_type == SumType::Abundant && !has_non_deficient_divisor[num],
+        };
     }
 
-    // Convenient to have 0 in the array, so give some arbitrary value
-    numbers[0] = SumType::Deficient;
     numbers
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::get_abundant_numbers::{get_extended_sum_type, get_sum_type};
 
     #[test]
     fn test_classify_numbers() {
@@ -81,6 +115,27 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_classify_matches_get_sum_type() {
+        let classified = classify_numbers(10_000);
+
+        for (num, &sum_type) in classified.iter().enumerate().skip(1) {
+            assert_eq!(sum_type, get_sum_type(num as u64), "{num}");
+        }
+    }
+
+    #[test]
+    fn test_classify_numbers_extended() {
+        let classified = classify_numbers_extended(5_000);
+        let basic = classify_numbers(5_000);
+
+        assert_eq!(classified[0], ExtendedSumType::zero());
+        for num in 1..=5_000 {
+            assert_eq!(classified[num], get_extended_sum_type(num as u64), "{num}");
+            assert_eq!(classified[num].sum_type, basic[num], "{num}");
+        }
+    }
+
     #[test]
     fn test_collect_abundant_numbers() {
         let abundant_numbers = collect_abundant_numbers(60);
@@ -1,3 +1,4 @@
+use common::{factorize, Divisors};
 use std::collections::HashSet;
 use std::iter::once;
 
@@ -60,6 +61,15 @@ pub fn calculate_proper_divisors_doubled(num: u64) -> HashSet<u64> {
         .collect()
 }
 
+/// Calculate the proper divisors of a number in ascending order from its prime factorisation
+///
+/// The divisors are built up from the prime powers rather than found by division, so there are no
+/// duplicates to remove and no need to hash or sort them afterwards.
+pub fn calculate_proper_divisors_sorted(num: u64) -> impl Iterator<Item = u64> {
+    // 0 has no factorisation, so like 1 it is given no proper divisors
+    Divisors::proper(&factorize(num.max(1)))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -113,4 +123,16 @@ mod test {
             assert_eq!(divisors, sqrt_divisors, "{num} should be equal")
         }
     }
+
+    #[test]
+    fn test_calculate_proper_divisors_sorted() {
+        for num in 1..1000 {
+            let divisors: Vec<_> = calculate_proper_divisors(num).collect();
+            let sorted_divisors: Vec<_> = calculate_proper_divisors_sorted(num).collect();
+
+            assert_eq!(divisors, sorted_divisors, "{num} should be equal")
+        }
+
+        assert_eq!(calculate_proper_divisors_sorted(0).count(), 0);
+    }
 }
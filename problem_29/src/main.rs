@@ -1,9 +1,5 @@
+use common::factorize;
 use std::collections::HashSet;
-use std::ops::Rem;
-
-const PRIMES: [u16; 25] = [
-    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
-];
 
 fn main() {
     let max_value = 100;
@@ -17,74 +13,31 @@ fn main() {
 ///
 /// We make use of the fact that raising a number to a power is a multiplication of the powers in
 /// the prime factorisation of the number. We make use of this to break all the numbers in
-/// [2, max_value] into vectors of the powers of the prime factors and store these vectors
-/// and their multiples over [2, max_power] in a set.
-fn get_unique_values(max_value: u16, max_power: u16) -> usize {
+/// [2, max_value] into their prime factorisations and store these factorisations raised to each
+/// power in [2, max_power] in a set, so the values themselves never need to be computed.
+fn get_unique_values(max_value: u64, max_power: u32) -> usize {
     let mut set = HashSet::new();
     let starting_values = 2..=max_value;
 
     for value in starting_values {
-        let prime_factors = break_down_into_prime_factors(value);
+        let prime_factors = factorize(value);
 
         // Raising a number to a power ``n`` simply multiplies the powers of prime factors by ``n``.
         for power in 2..=max_power {
-            let power_multiple: Vec<u16> = prime_factors.iter().map(|&v| v * power).collect();
-            set.insert(power_multiple);
+            set.insert(prime_factors.pow(power));
         }
     }
 
     set.len()
 }
 
-/// Break a number down into its prime factors
-///
-/// Returns an array with the ``i``th entry the power of the given prime (given in ``PRIMES``).
-/// As we've hardcoded the primes, this will only work with values up to 100.
-fn break_down_into_prime_factors(value: u16) -> [u16; 25] {
-    if value > 100 {
-        panic!("Number must be smaller than 100!");
-    }
-
-    let mut value = value;
-    let mut prime_factors = [0; 25];
-
-    for (power, prime) in prime_factors.iter_mut().zip(PRIMES) {
-        loop {
-            if value.rem(prime) != 0 {
-                break
-            }
-            value /= prime;
-            *power += 1;
-        }
-    }
-
-    prime_factors
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
 
-    /// Debugging function for displaying the prime decomposition vector
-    fn pretty_format_factors(factor_array: &[u16], starting_val: u16) -> String {
-        let factor_string = factor_array
-            .iter()
-            .enumerate()
-            .filter(|(_, &val)| val != 0)
-            .map(|(index_, &val)| {
-                format!("{}^{val}", PRIMES[index_])
-            })
-            .reduce(|a, b| format!("{a} × {b}"))
-            .unwrap();
-
-        format!("{starting_val} = {factor_string}")
-    }
-
     #[test]
     fn test_print_primes() {
-        let value = 90;
-        let prime_factors = break_down_into_prime_factors(value);
-        let pretty_fmt = pretty_format_factors(&prime_factors, value);
+        let pretty_fmt = factorize(90u64).to_string();
 
         let pretty_fmt_expected = "90 = 2^1 × 3^2 × 5^1".to_string();
         assert_eq!(pretty_fmt, pretty_fmt_expected)
@@ -92,30 +45,23 @@ mod test {
 
     #[test]
     fn power_breakdown_4() {
-        let prime_factors = break_down_into_prime_factors(4);
+        let prime_factors = factorize(4u64);
 
-        let mut prime_factors_expected = [0; 25];
-        prime_factors_expected[0] = 2;
-
-        assert_eq!(prime_factors, prime_factors_expected);
+        assert_eq!(prime_factors.factors(), [(2, 2)]);
     }
 
     #[test]
     fn power_breakdown_12() {
-        let prime_factors = break_down_into_prime_factors(12);
-
-        let mut prime_factors_expected = [0; 25];
-        prime_factors_expected[0] = 2;
-        prime_factors_expected[1] = 1;
+        let prime_factors = factorize(12u64);
 
-        assert_eq!(prime_factors, prime_factors_expected);
+        assert_eq!(prime_factors.factors(), [(2, 2), (3, 1)]);
+        assert_eq!(prime_factors.to_integer(), Some(12));
+    }
 
-        let total: u16 = prime_factors
-            .into_iter()
-            .enumerate()
-            .map(|(index_, value)| PRIMES[index_].pow(value as u32))
-            .product();
-        assert_eq!(total, 12);
+    #[test]
+    fn power_of_power() {
+        // 4^3 and 8^2 are both 2^6, so must be treated as the same entry
+        assert_eq!(factorize(4u64).pow(3), factorize(8u64).pow(2));
     }
 
     #[test]
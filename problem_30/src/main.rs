@@ -1,3 +1,4 @@
+use common::digits_be;
 use itertools::{repeat_n, Itertools};
 use std::time::Instant;
 
@@ -105,23 +106,27 @@ fn digit_power_sum(num: u32, power: u32) -> u32 {
     digits.iter().map(|x| x.pow(power)).sum()
 }
 
-fn to_digits(num: u32) -> Vec<u32> {
-    // Break the number into digits
-    num.to_string()
-        .chars()
-        .map(|d| d.to_digit(10).unwrap())
-        .collect()
+fn to_digits_div(num: u32) -> Vec<u32> {
+    digits_be(num, 10).collect()
 }
 
-fn to_digits_div(num: u32) -> Vec<u32> {
-    let n_digits = (num as f32).log10() as u32 + 1;
-    let mut digits = Vec::with_capacity(n_digits as usize);
-    let mut n = num;
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_digit_power_sum() {
+        assert_eq!(digit_power_sum(1634, 4), 1634);
+        // Powers of ten have to keep their leading 1
+        assert_eq!(digit_power_sum(1000, 4), 1);
+        assert_eq!(digit_power_sum(100_000, 5), 1);
+    }
 
-    for _ in 0..n_digits {
-        digits.push(n % 10);
-        n /= 10;
+    #[test]
+    fn test_approaches_agree() {
+        // 4150 + 4151 + 54748 + 92727 + 93084 + 194979
+        assert_eq!(brute_force_approach(5), 443_839);
+        assert_eq!(explicit_powers(), 443_839);
+        assert_eq!(power_approach(), 443_839);
     }
-    digits.reverse();
-    digits
 }
@@ -77,7 +77,7 @@ fn explicit_factorials() -> u32 {
 
                         for (num_f, pow_f) in digit_powers.iter() {
                             let power = power_e + pow_f;
-                            let digit = digit_e + num_f * 1;
+                            let digit = digit_e + num_f;
 
                             if digit == power {
                                 matches += digit;
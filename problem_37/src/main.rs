@@ -1,6 +1,8 @@
+use common::{from_digits, is_prime};
+
 fn main() {
     let digits = generate_candidates();
-    let res: Vec<_> = digits.iter().map(|v| sum_digits(v)).collect();
+    let res: Vec<_> = digits.iter().map(|v| from_digit_slice(v)).collect();
 
     let n_digits = res.len();
     let total: u32 = res.iter().sum();
@@ -61,31 +63,16 @@ fn generate_candidates() -> Vec<Vec<u32>> {
 fn is_right_prime(v: &[u32]) -> bool {
     let n_digits = v.len();
 
-    (1..n_digits).all(|i| is_prime(sum_digits(&v[i..])))
+    (1..n_digits).all(|i| is_prime(from_digit_slice(&v[i..])))
 }
 
 fn are_digits_prime(v: &[u32]) -> bool {
-    is_prime(sum_digits(v))
-}
-
-fn sum_digits(v: &[u32]) -> u32 {
-    v.iter()
-        .enumerate()
-        .map(|(index, val)| 10_u32.pow(index as u32) * val)
-        .sum()
+    is_prime(from_digit_slice(v))
 }
 
-fn is_prime(n: u32) -> bool {
-    if n <= 3 {
-        return n > 1;
-    } else if n % 2 == 0 || n % 3 == 0 {
-        return false;
-    }
-
-    let limit = (n as f64).sqrt() as u32;
-    !(5..=limit)
-        .step_by(6)
-        .any(|i| n % i == 0 || n % (i + 2) == 0)
+/// Return the number with the given digits, least significant first
+fn from_digit_slice(v: &[u32]) -> u32 {
+    from_digits(v.iter().rev().copied(), 10).unwrap()
 }
 
 /// Add a valid digit to the end of the number, with the end result being left-truncatable.
@@ -138,7 +125,7 @@ fn add_valid_digit_mid(mut candidate: Vec<u32>) -> Vec<Vec<u32>> {
 /// For now we check if the number is divisible by three, but this can be made more complex
 fn is_valid(candidate: &[u32]) -> bool {
     let digit_sum: u32 = candidate.iter().sum();
-    digit_sum % 3 != 0
+    !digit_sum.is_multiple_of(3)
 }
 
 #[cfg(test)]
@@ -150,7 +137,7 @@ mod test {
         let mut main_vec = Vec::new();
 
         for v in vecs.into_iter() {
-            main_vec.push(Vec::from(v.to_owned()));
+            main_vec.push(v.to_owned());
         }
 
         main_vec
@@ -234,7 +221,7 @@ mod test {
 
     #[test]
     fn test_primes() {
-        let primes = vec![
+        let primes: Vec<u32> = vec![
             373, 563, 593, 607, 653, 733, 947, 977, 1103, 1123, 1187, 1223, 1367, 1511, 1747, 1753,
             1907, 2287, 2417, 2677, 2903, 2963, 3307, 3313,
         ];
@@ -243,7 +230,7 @@ mod test {
 
     #[test]
     fn is_composite() {
-        let composite = vec![
+        let composite: Vec<u32> = vec![
             4, 6, 8, 9, 10, 12, 14, 15, 16, 18, 20, 21, 22, 24, 25, 26, 27, 28, 30, 32, 33, 34, 35,
             36, 38, 39, 40, 42, 44, 45, 46, 48, 49, 50, 51, 52, 54, 55, 56, 57, 58, 60, 62, 63, 64,
             65, 66, 68, 69, 70, 72, 74, 75, 76, 77, 78, 80, 81, 82, 84, 85, 86, 87, 88, 90, 91, 92,
@@ -262,12 +249,12 @@ mod test {
     }
 
     #[test]
-    fn test_sum_digits() {
-        let digits = vec![vec![1, 9, 5, 6], vec![7, 9, 8, 6]];
+    fn test_from_digit_slice() {
+        let digits = [vec![1, 9, 5, 6], vec![7, 9, 8, 6]];
         let expected_sums = vec![6591, 6897];
 
         for (digits, expected) in digits.iter().zip(expected_sums) {
-            let total = sum_digits(digits);
+            let total = from_digit_slice(digits);
             assert_eq!(total, expected);
         }
     }
@@ -7,11 +7,10 @@ use std::collections::HashSet;
 ///
 /// At least for the first 100_000 pentagonal numbers there is only one pair that satisfies this
 /// condition, namely (7042750, 1560090) with D = 5482660
-
 fn main() {
     let max_index: usize = 100000;
     let pentagonals: Vec<u64> = (1..=max_index).map(|v| get_pentagonal(v) as u64).collect();
-    let mut pentagonal_set: HashSet<_> = pentagonals.clone().into_iter().collect();
+    let pentagonal_set: HashSet<_> = pentagonals.clone().into_iter().collect();
 
     let mut min_diff = u64::MAX;
     let mut delta_steps: Option<usize> = None;
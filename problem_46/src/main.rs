@@ -1,3 +1,4 @@
+use common::PrimeCache;
 use std::time::Instant;
 // Problem 46: Goldbach's other conjecture
 
@@ -11,8 +12,10 @@ use std::time::Instant;
 
 fn main() {
     let now = Instant::now();
+    // The search has no upper bound, so let the sieve grow as we go
+    let primes = PrimeCache::new();
     let result = (3..).step_by(2)
-        .find(|&n| !test_goldbach(n)).unwrap();
+        .find(|&n| !test_goldbach(n, &primes)).unwrap();
     let elapsed = now.elapsed();
 
     println!("The smallest odd composite that cannot be written as the sum of a prime and twice a square is {}", result);
@@ -20,8 +23,8 @@ fn main() {
 }
 
 /// Return if the number can be written as the sum of a prime and twice a square
-fn test_goldbach(n: u32) -> bool {
-    if is_prime(n) {
+fn test_goldbach(n: u32, primes: &PrimeCache) -> bool {
+    if primes.is_prime(n as u64) {
         // If n is prime then we say it is trivially true
         return true;
     }
@@ -29,20 +32,5 @@ fn test_goldbach(n: u32) -> bool {
     // We only need to check up to the square root of n/2
     // We might have chosen to check if N - P for some prime P is a square, but that would involve far more checks.
     let limit = ((n as f64)/2.0).sqrt() as u32;
-    (1..=limit).any(|i| is_prime(n - 2 * i * i))
+    (1..=limit).any(|i| primes.is_prime((n - 2 * i * i) as u64))
 }
-
-
-fn is_prime(n: u32) -> bool {
-    if n <= 3 {
-        return n > 1;
-    } else if n % 2 == 0 || n % 3 == 0 {
-        return false;
-    }
-
-    let limit = (n as f64).sqrt() as u32;
-    !(5..=limit)
-        .step_by(6)
-        .any(|i| n % i == 0 || n % (i + 2) == 0)
-}
-
@@ -1,3 +1,4 @@
+use common::SpfSieve;
 use std::collections::{HashSet, VecDeque};
 use std::time::Instant;
 
@@ -21,11 +22,11 @@ fn main() {
 }
 
 fn get_consecutive_prime_factors(n: usize, num_factors: usize) -> Option<usize> {
-    let primes = get_primes_up_to(n-4);
+    let sieve: SpfSieve = SpfSieve::new(n);
     let mut previous_factors = VecDeque::with_capacity(num_factors);
 
     for i in 2..=n {
-        let factors = break_down_into_prime_factors(i as u64, &primes);
+        let factors = break_down_into_prime_factors(i as u64, &sieve);
 
         if factors.len() == num_factors {
             previous_factors.push_back(factors);
@@ -54,74 +55,26 @@ fn get_consecutive_prime_factors(n: usize, num_factors: usize) -> Option<usize>
     None
 }
 
-fn get_primes_up_to(n: usize) -> Vec<u64> {
-    let mut primes = Vec::new();
-    let mut is_prime = vec![true; n + 1];
-
-    for i in 2..=n {
-        if is_prime[i] {
-            primes.push(i as u64);
-
-            let mut j = i * i;
-            while j <= n {
-                is_prime[j] = false;
-                j += i;
-            }
-        }
-    }
-
-    primes
-}
-
 /// Breaks down a number into its prime factors.
 ///
 /// We return the prime numbers multiplied by their powers, e.g. 644 = 2^2 * 7 * 23 = {4, 7, 23} to match their
-/// use in the problem.
-fn break_down_into_prime_factors(n: u64, primes: &[u64]) -> HashSet<u64> {
-    let mut factors = HashSet::new();
-    let mut n = n;
-
-    let limit = (n as f64).sqrt() as u64;
-
-    for prime in primes {
-        if *prime > limit {
-            break;
-        }
-        let mut cumulative = 1;
-
-        while n % prime == 0 {
-            // Insert the prime factor to its power
-            n /= prime;
-            cumulative *= prime;
-
-            if n == 1 {
-                // If we have reached 1, then we have found all the prime factors
-                factors.insert(cumulative);
-                return factors;
-            }
-        }
-
-        if cumulative > 1 {
-            factors.insert(cumulative);
-        }
-    }
-
-    // If we get here, then we have a prime factor larger than the square root of n.
-    factors.insert(n);
-    factors
+/// use in the problem. The smallest prime factor sieve makes this O(log n), rather than trial dividing every number.
+fn break_down_into_prime_factors(n: u64, sieve: &SpfSieve) -> HashSet<u64> {
+    sieve.prime_power_parts(n).into_iter().collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common::get_primes_up_to;
     use test_case::test_case;
 
     #[test_case(14, &[2, 7])]
     #[test_case(644, &[4, 7, 23])]
     #[test_case(646, &[2, 17, 19])]
     fn test_break_down_into_prime_factors(n: u64, factors: &[u64]) {
-        let primes = [2, 3, 5, 7, 11, 13, 17, 19, 23];
-        let actual = break_down_into_prime_factors(n, &primes);
+        let sieve = SpfSieve::new(n as usize);
+        let actual = break_down_into_prime_factors(n, &sieve);
 
         let expected = factors.iter().copied().collect::<HashSet<_>>();
         assert_eq!(actual, expected)
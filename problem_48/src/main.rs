@@ -20,11 +20,9 @@ fn main() {
 }
 
 fn truncated_power_series_sum(max: u64) -> u64 {
-    let sum = (1..=max)
+    (1..=max)
         .map(|x| memory_efficient_mod_exp(x, x, 10_000_000_000))
-        .reduce(|acc, b| (acc + b) % 100_000_000_000).unwrap();
-
-    sum
+        .reduce(|acc, b| (acc + b) % 100_000_000_000).unwrap()
 }
 
 // This is a memory efficient version of the mod_exp function, but it's
@@ -34,28 +32,5 @@ fn memory_efficient_mod_exp(base: u64, exp: u64, modulo: u64) -> u64 {
         return 0;
     }
 
-    (0..exp).fold(1, |acc, a| (acc * base) % modulo)
-}
-
-// This is a more efficient version of the above function, but it overflows
-// for large values.
-fn right_to_left_mod_exp(base: u64, exp: u64, modulo: u64) -> u64 {
-    if modulo == 1 {
-        return 0;
-    }
-
-    let mut result = 1;
-    let mut base = base % modulo;
-    let mut exp = exp;
-
-    while exp > 0 {
-        if exp % 2 == 1 {
-            result = (result * base) % modulo;
-        }
-
-        exp >>= 1;
-        base = (base * base) % modulo;
-    }
-
-    result
+    (0..exp).fold(1, |acc, _| (acc * base) % modulo)
 }
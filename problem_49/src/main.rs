@@ -17,6 +17,7 @@ why this would need to be the case.
 */
 
 
+use common::{group_by_signature, DigitSignature};
 use itertools::Itertools;
 use std::collections::HashMap;
 use std::fmt::Display;
@@ -41,7 +42,7 @@ fn main() {
 
 }
 
-fn remove_duplicates(digit_map: HashMap<Digits, Vec<u32>>) -> Vec<(u32, u32, u32)>{
+fn remove_duplicates(digit_map: HashMap<DigitSignature, Vec<u32>>) -> Vec<(u32, u32, u32)>{
     let mut results = Vec::new();
 
     for (_digits, primes) in digit_map {
@@ -69,9 +70,9 @@ fn remove_duplicates(digit_map: HashMap<Digits, Vec<u32>>) -> Vec<(u32, u32, u32
         // Sometimes we have two pairs that have the same distances but are not connected, so we need
         // to filter those out
         for (_distance, pairs) in distance_map {
-            pairs.iter().enumerate().for_each(|(i, pair)| {
+            pairs.iter().for_each(|pair| {
 
-                let pairs = pairs.iter().find(|p_lower_| p_lower_.is_upper_limit(&pair));
+                let pairs = pairs.iter().find(|p_lower_| p_lower_.is_upper_limit(pair));
                 if let Some(p_lower) = pairs {
                     results.push((p_lower.p1, p_lower.p2, pair.p2));
                 }
@@ -103,17 +104,15 @@ impl Display for PrimePair {
 
 /// Create a hashmap of digits to primes
 ///
-/// The key is the (sorted) digits of the prime, and the value is a vector of all the primes that
-/// have those digits, sorted by value
-fn split_digits() -> HashMap<Digits, Vec<u32>> {
+/// The key is the signature of the digits of the prime, and the value is a vector of all the primes
+/// that have those digits, sorted by value
+fn split_digits() -> HashMap<DigitSignature, Vec<u32>> {
     let primes = get_primes_up_to(10000)
         .into_iter()
-        .skip_while(|n| *n < 1000);
+        .skip_while(|n| *n < 1000)
+        .map(|n| n as u32);
 
-    primes.map(|n| {
-        let digits = Digits::from_number(n as u32);
-        (digits, n as u32)
-    }).into_group_map()
+    group_by_signature(primes, 10)
 }
 
 fn get_primes_up_to(n: usize) -> Vec<u64> {
@@ -134,37 +133,3 @@ fn get_primes_up_to(n: usize) -> Vec<u64> {
 
     primes
 }
-
-/// Sorted storage of digits from four digit number
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
-struct Digits {
-    a: u32,
-    b: u32,
-    c: u32,
-    d: u32,
-}
-
-impl Digits {
-    fn from_number(number: u32) -> Digits {
-        let a = number / 1000;
-        let b = (number - a * 1000) / 100;
-        let c = (number - a * 1000 - b * 100) / 10;
-        let d = number - a * 1000 - b * 100 - c * 10;
-
-        let mut digits = [a, b, c, d];
-        digits.sort();
-
-        Digits {
-            a: digits[0],
-            b: digits[1],
-            c: digits[2],
-            d: digits[3],
-        }
-    }
-}
-
-impl Display for Digits {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "[{}, {}, {}, {}]", self.a, self.b, self.c, self.d)
-    }
-}
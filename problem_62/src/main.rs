@@ -0,0 +1,129 @@
+use common::DigitSignature;
+use std::collections::HashMap;
+use std::time::Instant;
+
+// Day 62: Cubic permutations
+//
+// The cube, 41063625 (345^3), can be permuted to produce two other cubes: 56623104 (384^3) and
+// 66430125 (405^3). In fact, 41063625 is the smallest cube which has exactly three permutations of
+// its digits which are also cube.
+//
+// Find the smallest cube for which exactly five permutations of its digits are cube.
+
+fn main() {
+    println!("Problem 62");
+
+    let num_permutations = 5;
+    let exponent = 3;
+
+    let start = Instant::now();
+    let answer = smallest_power_permutation(num_permutations, exponent)
+        .expect("No cube with exactly 5 permutations fits in a u128");
+    let duration = start.elapsed();
+
+    println!("Answer: {}", answer);
+    println!("Time taken: {:?}", duration);
+}
+
+/// Return the smallest ``exponent``-th power for which exactly ``num_permutations`` permutations of
+/// its digits are also ``exponent``-th powers, itself included
+///
+/// Permutations have the same number of digits, so the powers are grouped by their digit signature
+/// one digit length at a time. A group can only be judged once every power of that length has been
+/// seen, as a later power may push it past ``num_permutations``. The powers are computed in
+/// ``u128``, so that the search can carry on past ``u64``, and ``None`` is returned if it runs out
+/// of room there too. The 39 digit powers only partly fit, so their groups are never judged.
+fn smallest_power_permutation(num_permutations: usize, exponent: u32) -> Option<u128> {
+    assert!(num_permutations > 0, "a power is always a permutation of itself");
+    assert!(exponent > 0, "every 0th power is 1, so the digit lengths never grow");
+
+    let mut groups: HashMap<DigitSignature, Vec<u128>> = HashMap::new();
+    let mut num_digits = 1;
+
+    for base in 1u128.. {
+        let power = base.checked_pow(exponent);
+
+        if power.is_none_or(|power| num_digits_of(power) > num_digits) {
+            if power.is_none() && 10u128.checked_pow(num_digits).is_none() {
+                // The powers of this length past u128::MAX were never seen, so the groups may be
+                // missing members
+                return None;
+            }
+
+            // Every power with ``num_digits`` digits has been seen
+            let smallest = groups
+                .values()
+                .filter(|powers| powers.len() == num_permutations)
+                .map(|powers| powers[0])
+                .min();
+            if smallest.is_some() {
+                return smallest;
+            }
+
+            groups.clear();
+        }
+
+        let power = power?;
+        // Large exponents can skip over some lengths altogether
+        num_digits = num_digits_of(power);
+        groups.entry(DigitSignature::new(power, 10)).or_default().push(power);
+    }
+
+    unreachable!()
+}
+
+fn num_digits_of(n: u128) -> u32 {
+    n.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_example() {
+        assert_eq!(smallest_power_permutation(3, 3), Some(41_063_625));
+    }
+
+    #[test]
+    fn test_full_solution() {
+        assert_eq!(smallest_power_permutation(5, 3), Some(127_035_954_683));
+    }
+
+    #[test]
+    fn test_squares() {
+        // 144 = 12^2 and 441 = 21^2 share their digits, and 169, 196 and 961 are 13^2, 14^2 and 31^2
+        assert_eq!(smallest_power_permutation(2, 2), Some(144));
+        assert_eq!(smallest_power_permutation(3, 2), Some(169));
+
+        // Every single digit power is its own group
+        assert_eq!(smallest_power_permutation(1, 2), Some(1));
+    }
+
+    #[test]
+    fn test_beyond_u64() {
+        // No three fifth powers share their digits until 10001^5, which has 21 digits
+        assert_eq!(smallest_power_permutation(3, 5), Some(100_050_010_001_000_050_001));
+        assert_eq!(smallest_power_permutation(4, 5), Some(2_809_732_043_385_544_072_671_657));
+
+        // Exhausting u128 gives up rather than overflowing
+        assert_eq!(smallest_power_permutation(1_000, 40), None);
+    }
+
+    #[test]
+    fn test_overflow_boundary() {
+        // 10001^7 has 36 digits, and no group of seventh powers reaches 5 before the 39 digit
+        // ones, which are cut short at u128::MAX and can't be judged
+        assert_eq!(
+            smallest_power_permutation(4, 7),
+            Some(100_007_000_210_003_500_035_000_210_000_700_001)
+        );
+        assert_eq!(smallest_power_permutation(5, 7), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "every 0th power is 1")]
+    fn test_zero_exponent() {
+        smallest_power_permutation(2, 0);
+    }
+}
@@ -0,0 +1,211 @@
+use common::{get_digits_sorted, get_primes_up_to};
+use std::time::Instant;
+
+// Day 69: Totient maximum
+//
+// Euler's totient function φ(n) counts the numbers less than n that are relatively prime to n.
+// Find the value of n <= 1,000,000 for which n/φ(n) is a maximum.
+//
+// Problem 70 asks the opposite question: find the 1 < n < 10^7 for which φ(n) is a permutation of
+// the digits of n and n/φ(n) is a minimum.
+
+/// The largest prime whose primorial fits in a ``u64``, as multiplying in 53 would overflow
+const MAX_PRIMORIAL_PRIME: usize = 47;
+
+fn main() {
+    println!("Problem 69");
+
+    let start = Instant::now();
+    let answer = totient_maximum(1_000_000);
+    let duration = start.elapsed();
+
+    println!("Answer: {}", answer);
+    println!("Time taken: {:?}", duration);
+
+    println!("Problem 70");
+
+    let start = Instant::now();
+    let answer = totient_permutation_minimum(10_000_000 - 1)
+        .expect("No n below 10^7 has a totient that is a permutation of its digits");
+    let duration = start.elapsed();
+
+    println!("Answer: {}", answer);
+    println!("Time taken: {:?}", duration);
+}
+
+/// Return the ``n <= limit`` for which n/φ(n) is a maximum
+///
+/// n/φ(n) is the product of p/(p - 1) over the distinct primes dividing n, so it only depends on
+/// which primes divide n, and every extra prime increases it. The smallest primes contribute the
+/// most, so the answer is the largest primorial 2 × 3 × 5 × ... that fits under the limit.
+fn totient_maximum(limit: u64) -> u64 {
+    let mut primorial = 1u64;
+
+    for p in get_primes_up_to(MAX_PRIMORIAL_PRIME) {
+        match primorial.checked_mul(p) {
+            Some(next) if next <= limit => primorial = next,
+            _ => break,
+        }
+    }
+
+    primorial
+}
+
+/// Return the ``1 < n <= limit`` with n/φ(n) a minimum, among those where φ(n) is a permutation of
+/// the digits of n
+///
+/// Small ratios need few, large prime factors, so the numbers are built up from their prime
+/// factors in a depth first search, starting from the smallest prime factors just below the square
+/// root of the limit. This soon finds a product of two primes close to the square root, and as
+/// adding a prime only increases the ratio, any branch that can't beat the best ratio so far is cut
+/// off. The search is exact, and only visits a tiny fraction of the numbers up to the limit.
+fn totient_permutation_minimum(limit: u64) -> Option<u64> {
+    // Every composite n has a prime factor no larger than n / 2
+    let mut search = PermutationSearch {
+        limit,
+        primes: get_primes_up_to(limit as usize / 2),
+        best: None,
+    };
+
+    // The root is the smallest prime factor, and is at most sqrt(limit) unless n is itself prime.
+    // φ(p) = p - 1 only changes the last digit of an odd prime, so primes never qualify.
+    let max_root = search.primes.partition_point(|&p| p * p <= limit);
+    for index in (0..max_root).rev() {
+        let p = search.primes[index];
+        if !search.beats_best(p, p - 1) {
+            // Smaller primes only give larger ratios
+            break;
+        }
+        search.extend(p, p - 1, index);
+    }
+
+    search.best.map(|(n, _)| n)
+}
+
+/// State of the search for ``totient_permutation_minimum``
+struct PermutationSearch {
+    limit: u64,
+    primes: Vec<u64>,
+    /// The best ``(n, φ(n))`` found so far
+    best: Option<(u64, u64)>,
+}
+
+impl PermutationSearch {
+    /// Return if n/φ(n) is strictly smaller than the best ratio found so far
+    fn beats_best(&self, n: u64, phi: u64) -> bool {
+        match self.best {
+            Some((best_n, best_phi)) => {
+                (n as u128) * (best_phi as u128) < (best_n as u128) * (phi as u128)
+            }
+            None => true,
+        }
+    }
+
+    /// Visit ``n``, and every multiple of it whose other prime factors are no smaller than
+    /// ``primes[index]``, the largest prime factor of ``n``
+    fn extend(&mut self, n: u64, phi: u64, index: usize) {
+        if self.beats_best(n, phi) && is_permutation(n, phi) {
+            self.best = Some((n, phi));
+        }
+
+        // Another power of the largest prime leaves the ratio unchanged
+        let p = self.primes[index];
+        if n <= self.limit / p {
+            self.extend(n * p, phi * p, index);
+        }
+
+        // Larger primes give smaller ratios, so try them first and stop at the first that fails
+        let max_index = self.primes.partition_point(|&q| q <= self.limit / n);
+        for next in (index + 1..max_index).rev() {
+            let q = self.primes[next];
+            if !self.beats_best(n * q, phi * (q - 1)) {
+                break;
+            }
+            self.extend(n * q, phi * (q - 1), next);
+        }
+    }
+}
+
+/// Return if ``a`` and ``b`` have the same digits in some order
+fn is_permutation(a: u64, b: u64) -> bool {
+    get_digits_sorted(a) == get_digits_sorted(b)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use common::{multiplicative_table, Totient};
+
+    /// Compare n/φ(n) between two numbers, using their totients from ``phi``
+    fn ratio_cmp(a: usize, b: usize, phi: &[u64]) -> std::cmp::Ordering {
+        (a as u128 * phi[b] as u128).cmp(&(b as u128 * phi[a] as u128))
+    }
+
+    #[test]
+    fn test_example() {
+        // From the problem: n = 6 gives the maximum n/φ(n) = 3 for n <= 10
+        assert_eq!(totient_maximum(10), 6);
+        assert_eq!(totient_maximum(1), 1);
+    }
+
+    #[test]
+    fn test_maximum_matches_brute_force() {
+        let phi = multiplicative_table(3_000, &Totient);
+
+        for limit in 1..=3_000 {
+            // Multiples of the primorial share its ratio, so take the first with the largest ratio
+            let expected = (1..=limit)
+                .reduce(|best, n| if ratio_cmp(n, best, &phi).is_gt() { n } else { best })
+                .unwrap();
+
+            assert_eq!(totient_maximum(limit as u64), expected as u64, "{limit}");
+        }
+    }
+
+    #[test]
+    fn test_full_solution() {
+        assert_eq!(totient_maximum(1_000_000), 510_510);
+        assert_eq!(totient_maximum(100_000_000), 9_699_690);
+
+        // 2 × 3 × ... × 47 is the largest primorial a u64 can hold
+        assert_eq!(totient_maximum(u64::MAX), 614_889_782_588_491_410);
+    }
+
+    #[test]
+    fn test_permutation_example() {
+        // From problem 70: φ(87109) = 79180
+        assert!(is_permutation(87_109, 79_180));
+        assert!(!is_permutation(87_109, 87_108));
+    }
+
+    #[test]
+    fn test_permutation_matches_brute_force() {
+        let max_limit = 200_000;
+        let phi = multiplicative_table(max_limit, &Totient);
+
+        let mut expected = None;
+        for n in 2..=max_limit {
+            if is_permutation(n as u64, phi[n])
+                && expected.is_none_or(|best| ratio_cmp(n, best, &phi).is_lt())
+            {
+                expected = Some(n);
+            }
+
+            if n < 5_000 || n.is_multiple_of(9_973) || n == max_limit {
+                let found = totient_permutation_minimum(n as u64).map(|m| m as usize);
+                assert_eq!(found.is_some(), expected.is_some(), "{n}");
+
+                // Any n with the same ratio is as good an answer
+                if let (Some(found), Some(expected)) = (found, expected) {
+                    assert!(is_permutation(found as u64, phi[found]), "{n}");
+                    assert!(ratio_cmp(found, expected, &phi).is_eq(), "{n}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_permutation_full_solution() {
+        assert_eq!(totient_permutation_minimum(10_000_000 - 1), Some(8_319_823));
+    }
+}